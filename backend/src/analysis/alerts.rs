@@ -0,0 +1,492 @@
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use chrono::{DateTime, FixedOffset, NaiveDate, TimeDelta};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+
+use super::fnv1a;
+use crate::mock_data::cities::{self, City};
+use crate::mock_data::weather::{self, WeatherData};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertKind {
+    Heat,
+    Frost,
+    HighWind,
+    HeavyRain,
+    HighUv,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Moderate,
+    Severe,
+}
+
+/// A period in which a forecast value crosses one of the city's thresholds.
+#[derive(Debug, Clone, Serialize)]
+pub struct Alert {
+    /// Stable for the same city, kind and local day, so an alert that is
+    /// still in force keeps its id as the evaluation moves on.
+    pub id: String,
+    pub city: String,
+    pub kind: AlertKind,
+    pub severity: Severity,
+    /// In the city's time zone; `end` is exclusive.
+    pub start: String,
+    pub end: String,
+    /// The most extreme value in the period, in metric units.
+    pub peak: f64,
+    pub message: String,
+}
+
+/// The values at which an alert becomes moderate and severe. For frost the
+/// alert fires at or below them; for everything else at or above.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Levels {
+    pub moderate: f64,
+    pub severe: f64,
+}
+
+/// Thresholds for every kind of alert.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Thresholds {
+    /// Feels-like temperature.
+    pub heat_c: Levels,
+    /// Air temperature.
+    pub frost_c: Levels,
+    pub wind_kmh: Levels,
+    /// Rain over one local day.
+    pub rain_mm: Levels,
+    pub uv_index: Levels,
+}
+
+/// Regional adjustments; kinds left out keep the default thresholds.
+#[derive(Debug, Clone, Default, Deserialize)]
+struct RegionThresholds {
+    heat_c: Option<Levels>,
+    frost_c: Option<Levels>,
+    wind_kmh: Option<Levels>,
+    rain_mm: Option<Levels>,
+    uv_index: Option<Levels>,
+}
+
+/// Default thresholds plus overrides keyed by ISO country code, so a heat
+/// alert in Dubai takes more than one in London.
+#[derive(Debug, Clone, Deserialize)]
+pub struct AlertRules {
+    default: Thresholds,
+    #[serde(default)]
+    regions: HashMap<String, RegionThresholds>,
+}
+
+impl AlertRules {
+    /// The rules bundled in `alert_thresholds.json`.
+    pub fn bundled() -> &'static Self {
+        static RULES: OnceLock<AlertRules> = OnceLock::new();
+        RULES.get_or_init(|| {
+            serde_json::from_str(include_str!("alert_thresholds.json"))
+                .expect("alert_thresholds.json must be valid")
+        })
+    }
+
+    /// Read rules in the format of `alert_thresholds.json` from `path`.
+    pub fn load(path: &std::path::Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path).map_err(|err| err.to_string())?;
+        serde_json::from_str(&text).map_err(|err| err.to_string())
+    }
+
+    /// The thresholds that apply in `city`'s country.
+    pub fn for_city(&self, city: &City) -> Thresholds {
+        let Some(region) = self.regions.get(&city.country_code.to_uppercase()) else {
+            return self.default;
+        };
+        Thresholds {
+            heat_c: region.heat_c.unwrap_or(self.default.heat_c),
+            frost_c: region.frost_c.unwrap_or(self.default.frost_c),
+            wind_kmh: region.wind_kmh.unwrap_or(self.default.wind_kmh),
+            rain_mm: region.rain_mm.unwrap_or(self.default.rain_mm),
+            uv_index: region.uv_index.unwrap_or(self.default.uv_index),
+        }
+    }
+}
+
+/// Evaluate the current weather of every registered city, keyed by city id.
+pub fn evaluate_cities(rules: &AlertRules) -> HashMap<String, Vec<Alert>> {
+    cities::cities()
+        .iter()
+        .map(|city| {
+            let alerts = evaluate(&weather::get_weather(city), &rules.for_city(city));
+            (city.id.clone(), alerts)
+        })
+        .collect()
+}
+
+/// One point in time the alerts are evaluated at: now, then each hour.
+struct Sample {
+    start: DateTime<FixedOffset>,
+    feels_like_c: f64,
+    temperature_c: f64,
+    wind_kmh: f64,
+    uv_index: f64,
+}
+
+impl Sample {
+    /// The value `kind` is judged on; rain is handled per day instead.
+    fn value(&self, kind: AlertKind) -> f64 {
+        match kind {
+            AlertKind::Heat => self.feels_like_c,
+            AlertKind::Frost => self.temperature_c,
+            AlertKind::HighWind => self.wind_kmh,
+            AlertKind::HighUv => self.uv_index,
+            AlertKind::HeavyRain => 0.0,
+        }
+    }
+}
+
+/// Alerts raised by the current conditions, the hourly forecast and, for
+/// rain, the daily totals, earliest first.
+pub fn evaluate(data: &WeatherData, thresholds: &Thresholds) -> Vec<Alert> {
+    let Ok(now) = DateTime::parse_from_rfc3339(&data.local_time) else {
+        return Vec::new();
+    };
+    let mut samples = vec![Sample {
+        start: now,
+        feels_like_c: data.current.feels_like_c,
+        temperature_c: data.current.temperature_c,
+        wind_kmh: data.current.wind_speed_kmh,
+        uv_index: data.current.uv_index as f64,
+    }];
+    samples.extend(data.hourly.iter().filter_map(|hour| {
+        Some(Sample {
+            start: DateTime::parse_from_rfc3339(&hour.time).ok()?,
+            feels_like_c: hour.feels_like_c,
+            temperature_c: hour.temperature_c,
+            wind_kmh: hour.wind_speed_kmh,
+            uv_index: hour.uv_index as f64,
+        })
+    }));
+
+    let mut alerts = Vec::new();
+    let hourly_kinds = [
+        (AlertKind::Heat, thresholds.heat_c),
+        (AlertKind::Frost, thresholds.frost_c),
+        (AlertKind::HighWind, thresholds.wind_kmh),
+        (AlertKind::HighUv, thresholds.uv_index),
+    ];
+    for (kind, levels) in hourly_kinds {
+        let values: Vec<f64> = samples.iter().map(|s| s.value(kind)).collect();
+        for (first, last) in runs(&values, |v| severity(kind, levels, v).is_some()) {
+            let peak = extreme(kind, &values[first..=last]);
+            let end = samples
+                .get(last + 1)
+                .map_or(samples[last].start + TimeDelta::hours(1), |next| next.start);
+            alerts.push(alert(data, kind, levels, samples[first].start, end, peak));
+        }
+    }
+
+    // Rain is judged per local day: the rest of today from the hourly
+    // forecast, then the daily totals.
+    if let Ok(tz) = data.timezone.parse::<Tz>() {
+        let today = now.date_naive();
+        let midnight = |date: NaiveDate| {
+            date.and_hms_opt(0, 0, 0)
+                .and_then(|t| t.and_local_timezone(tz).earliest())
+                .map(|t| t.fixed_offset())
+        };
+        let rest_of_today: f64 = data
+            .hourly
+            .iter()
+            .filter(|h| h.time.starts_with(&today.format("%Y-%m-%d").to_string()))
+            .map(|h| h.precipitation_mm)
+            .sum();
+        let mut days = vec![(now, today, rest_of_today)];
+        days.extend(data.forecast.iter().filter_map(|day| {
+            let date = NaiveDate::parse_from_str(&day.date, "%Y-%m-%d").ok()?;
+            Some((midnight(date)?, date, day.precipitation_mm))
+        }));
+        for (start, date, total) in days {
+            if severity(AlertKind::HeavyRain, thresholds.rain_mm, total).is_some() {
+                let end = midnight(date + TimeDelta::days(1)).unwrap_or(start);
+                let levels = thresholds.rain_mm;
+                alerts.push(alert(data, AlertKind::HeavyRain, levels, start, end, total));
+            }
+        }
+    }
+
+    alerts.sort_by(|a, b| a.start.cmp(&b.start).then(b.severity.cmp(&a.severity)));
+    alerts
+}
+
+fn alert(
+    data: &WeatherData,
+    kind: AlertKind,
+    levels: Levels,
+    start: DateTime<FixedOffset>,
+    end: DateTime<FixedOffset>,
+    peak: f64,
+) -> Alert {
+    let severity = severity(kind, levels, peak).unwrap_or(Severity::Moderate);
+    let peak = (peak * 10.0).round() / 10.0;
+    let detail = match kind {
+        AlertKind::Heat => format!("feels like up to {peak} °C"),
+        AlertKind::Frost => format!("temperatures down to {peak} °C"),
+        AlertKind::HighWind => format!("winds up to {peak} km/h"),
+        AlertKind::HeavyRain => format!("{peak} mm of rain expected"),
+        AlertKind::HighUv => format!("UV index up to {peak}"),
+    };
+    let name = match kind {
+        AlertKind::Heat => "heat",
+        AlertKind::Frost => "frost",
+        AlertKind::HighWind => "high wind",
+        AlertKind::HeavyRain => "heavy rain",
+        AlertKind::HighUv => "high UV",
+    };
+    let label = match severity {
+        Severity::Moderate => "Moderate",
+        Severity::Severe => "Severe",
+    };
+    let window = format!("{} to {}", start.format("%a %H:%M"), end.format("%a %H:%M"));
+    let id = format!("{}\n{:?}\n{}", data.city, kind, start.date_naive());
+    Alert {
+        id: format!("alert-{:016x}", fnv1a(id.as_bytes())),
+        city: data.city.clone(),
+        kind,
+        severity,
+        start: start.to_rfc3339(),
+        end: end.to_rfc3339(),
+        peak,
+        message: format!("{label} {name} in {}: {detail}, {window}.", data.city),
+    }
+}
+
+fn severity(kind: AlertKind, levels: Levels, value: f64) -> Option<Severity> {
+    let crosses = |threshold: f64| match kind {
+        AlertKind::Frost => value <= threshold,
+        _ => value >= threshold,
+    };
+    if crosses(levels.severe) {
+        Some(Severity::Severe)
+    } else if crosses(levels.moderate) {
+        Some(Severity::Moderate)
+    } else {
+        None
+    }
+}
+
+/// The most extreme value for `kind`: lowest for frost, highest otherwise.
+fn extreme(kind: AlertKind, values: &[f64]) -> f64 {
+    match kind {
+        AlertKind::Frost => values.iter().copied().fold(f64::MAX, f64::min),
+        _ => values.iter().copied().fold(f64::MIN, f64::max),
+    }
+}
+
+/// Index ranges (inclusive) of consecutive values matching `pred`.
+fn runs(values: &[f64], pred: impl Fn(f64) -> bool) -> Vec<(usize, usize)> {
+    let mut runs = Vec::new();
+    let mut start = None;
+    for (i, &value) in values.iter().enumerate() {
+        match (pred(value), start) {
+            (true, None) => start = Some(i),
+            (false, Some(first)) => {
+                runs.push((first, i - 1));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(first) = start {
+        runs.push((first, values.len() - 1));
+    }
+    runs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{Event, EventBus};
+    use crate::mock_data::cities;
+    use crate::mock_data::weather::{CurrentWeather, ForecastDay, HourlyForecast};
+
+    fn hour(
+        time: &str,
+        feels_like_c: f64,
+        wind_speed_kmh: f64,
+        precipitation_mm: f64,
+    ) -> HourlyForecast {
+        HourlyForecast {
+            time: time.to_string(),
+            temperature_c: feels_like_c,
+            feels_like_c,
+            dew_point_c: 10.0,
+            humidity: 50,
+            cloud_cover: 20,
+            precipitation_chance: 10,
+            precipitation_mm,
+            wind_speed_kmh,
+            uv_index: 3,
+            condition: "Sunny".to_string(),
+        }
+    }
+
+    fn day(date: &str, precipitation_mm: f64) -> ForecastDay {
+        ForecastDay {
+            date: date.to_string(),
+            high_c: 20.0,
+            low_c: 10.0,
+            condition: "Rain".to_string(),
+            precipitation_chance: 80,
+            precipitation_mm,
+            humidity: 80,
+            wind_speed_kmh: 10.0,
+        }
+    }
+
+    fn data() -> WeatherData {
+        WeatherData {
+            city: "Dubai".to_string(),
+            country: "UAE".to_string(),
+            latitude: 25.2,
+            longitude: 55.3,
+            timezone: "Asia/Dubai".to_string(),
+            local_time: "2026-07-20T10:30:00+04:00".to_string(),
+            current: CurrentWeather {
+                temperature_c: 38.0,
+                feels_like_c: 43.0,
+                humidity: 40,
+                wind_speed_kmh: 10.0,
+                wind_direction: "N".to_string(),
+                condition: "Sunny".to_string(),
+                uv_index: 9,
+                visibility_km: 20.0,
+                pressure_hpa: 1005,
+            },
+            forecast: vec![day("2026-07-21", 60.0), day("2026-07-22", 2.0)],
+            hourly: vec![
+                hour("2026-07-20T11:00:00+04:00", 48.0, 10.0, 0.0),
+                hour("2026-07-20T12:00:00+04:00", 44.0, 60.0, 0.0),
+                hour("2026-07-20T13:00:00+04:00", 30.0, 80.0, 0.0),
+                hour("2026-07-20T14:00:00+04:00", 30.0, 10.0, 0.0),
+            ],
+            last_updated: "2026-07-20T06:30:00+00:00".to_string(),
+        }
+    }
+
+    #[test]
+    fn groups_hours_into_alerts() {
+        let thresholds = AlertRules::bundled().for_city(cities::lookup("dubai").unwrap());
+        assert_eq!(thresholds.heat_c.moderate, 42.0);
+        let alerts = evaluate(&data(), &thresholds);
+        let kinds: Vec<(AlertKind, Severity, &str, &str)> = alerts
+            .iter()
+            .map(|a| (a.kind, a.severity, a.start.as_str(), a.end.as_str()))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (
+                    AlertKind::Heat,
+                    Severity::Severe,
+                    "2026-07-20T10:30:00+04:00",
+                    "2026-07-20T13:00:00+04:00"
+                ),
+                (
+                    AlertKind::HighUv,
+                    Severity::Moderate,
+                    "2026-07-20T10:30:00+04:00",
+                    "2026-07-20T11:00:00+04:00"
+                ),
+                (
+                    AlertKind::HighWind,
+                    Severity::Severe,
+                    "2026-07-20T12:00:00+04:00",
+                    "2026-07-20T14:00:00+04:00"
+                ),
+                (
+                    AlertKind::HeavyRain,
+                    Severity::Severe,
+                    "2026-07-21T00:00:00+04:00",
+                    "2026-07-22T00:00:00+04:00"
+                ),
+            ]
+        );
+        assert_eq!(alerts[0].peak, 48.0);
+        assert!(alerts[0].message.starts_with("Severe heat in Dubai"));
+    }
+
+    #[test]
+    fn evaluates_every_city() {
+        let rules = AlertRules::bundled();
+        let all = evaluate_cities(rules);
+        assert_eq!(all.len(), cities::cities().len());
+        assert!(cities::cities()
+            .iter()
+            .all(|city| all.contains_key(&city.id)));
+    }
+
+    #[test]
+    fn evaluating_twice_within_an_hour_publishes_once() {
+        let dubai = cities::lookup("dubai").unwrap();
+        let thresholds = AlertRules::bundled().for_city(dubai);
+        let bus = EventBus::new(Vec::new());
+        let publish = |now: &str| {
+            let alerts = evaluate(
+                &weather::get_weather_at(dubai, now.parse().unwrap()),
+                &thresholds,
+            );
+            let fresh = alerts
+                .iter()
+                .filter(|alert| {
+                    bus.publish(Event::new(
+                        "weather.alert",
+                        &alert.id,
+                        serde_json::Value::Null,
+                    ))
+                })
+                .count();
+            (alerts.len(), fresh)
+        };
+        // Midday in July: the UV index alone is enough for an alert.
+        let (raised, fresh) = publish("2026-07-20T08:05:00Z");
+        assert!(raised > 0);
+        assert_eq!(fresh, raised);
+        assert_eq!(publish("2026-07-20T08:50:00Z"), (raised, 0));
+    }
+
+    #[test]
+    fn regions_override_defaults() {
+        let rules = AlertRules::bundled();
+        let london = rules.for_city(cities::lookup("london").unwrap());
+        let paris = rules.for_city(cities::lookup("paris").unwrap());
+        assert_eq!(london.heat_c.moderate, 28.0);
+        assert_eq!(paris.heat_c.moderate, 32.0);
+        assert_eq!(london.frost_c.moderate, paris.frost_c.moderate);
+        let dubai = rules.for_city(cities::lookup("dubai").unwrap());
+        assert_eq!(
+            severity(AlertKind::Heat, paris.heat_c, 43.0),
+            Some(Severity::Severe)
+        );
+        assert_eq!(
+            severity(AlertKind::Heat, dubai.heat_c, 43.0),
+            Some(Severity::Moderate)
+        );
+    }
+
+    #[test]
+    fn frost_fires_below_threshold() {
+        let levels = AlertRules::bundled().default.frost_c;
+        assert_eq!(severity(AlertKind::Frost, levels, 1.0), None);
+        assert_eq!(
+            severity(AlertKind::Frost, levels, -1.0),
+            Some(Severity::Moderate)
+        );
+        assert_eq!(
+            severity(AlertKind::Frost, levels, -9.0),
+            Some(Severity::Severe)
+        );
+    }
+}
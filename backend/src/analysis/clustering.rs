@@ -0,0 +1,286 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use super::fnv1a;
+use crate::mock_data::news::NewsArticle;
+
+/// Number of hash functions in each MinHash signature.
+const SIGNATURE_LEN: usize = 128;
+
+/// Words per shingle.
+const SHINGLE_SIZE: usize = 2;
+
+/// Default estimated Jaccard similarity above which two articles are one story.
+pub const DEFAULT_THRESHOLD: f64 = 0.2;
+
+const STOPWORDS: &[&str] = &[
+    "a", "an", "and", "are", "as", "at", "by", "for", "from", "has", "in", "into", "is", "it",
+    "its", "of", "on", "or", "the", "to", "with",
+];
+
+/// A group of articles covering the same event.
+#[derive(Debug, Clone, Serialize)]
+pub struct Story {
+    pub id: String,
+    pub title: String,
+    pub category: String,
+    pub canonical_article_id: String,
+    pub sources: Vec<String>,
+    pub article_count: usize,
+    pub first_published: DateTime<Utc>,
+    pub last_published: DateTime<Utc>,
+    pub articles: Vec<NewsArticle>,
+}
+
+/// Group near-duplicate articles into stories, largest first.
+///
+/// Each article's title and summary are reduced to word shingles and a
+/// MinHash signature; pairs whose signatures agree on at least `threshold`
+/// of their positions are joined, transitively, into one story. The earliest
+/// published member becomes the canonical article. An article without any
+/// words to shingle has nothing to compare and stays a story of its own.
+pub fn cluster(articles: &[NewsArticle], threshold: f64) -> Vec<Story> {
+    let signatures: Vec<Option<Vec<u64>>> = articles
+        .iter()
+        .map(|a| signature(&shingles(&format!("{} {}", a.title, a.summary))))
+        .collect();
+
+    let mut parent: Vec<usize> = (0..articles.len()).collect();
+    for i in 0..articles.len() {
+        for j in i + 1..articles.len() {
+            let (Some(a), Some(b)) = (&signatures[i], &signatures[j]) else {
+                continue;
+            };
+            if similarity(a, b) >= threshold {
+                let (a, b) = (find(&mut parent, i), find(&mut parent, j));
+                parent[a.max(b)] = a.min(b);
+            }
+        }
+    }
+
+    let mut groups: Vec<Vec<usize>> = Vec::new();
+    let mut group_of_root = vec![usize::MAX; articles.len()];
+    for i in 0..articles.len() {
+        let root = find(&mut parent, i);
+        if group_of_root[root] == usize::MAX {
+            group_of_root[root] = groups.len();
+            groups.push(Vec::new());
+        }
+        groups[group_of_root[root]].push(i);
+    }
+
+    let mut stories: Vec<Story> = groups
+        .into_iter()
+        .map(|members| {
+            let mut members: Vec<NewsArticle> =
+                members.into_iter().map(|i| articles[i].clone()).collect();
+            members.sort_by(|a, b| (&a.published_at, &a.id).cmp(&(&b.published_at, &b.id)));
+            let canonical = &members[0];
+
+            let mut sources: Vec<String> = Vec::new();
+            for article in &members {
+                if !sources.contains(&article.source) {
+                    sources.push(article.source.clone());
+                }
+            }
+
+            Story {
+                id: format!("story-{}", canonical.id),
+                title: canonical.title.clone(),
+                category: canonical.category.clone(),
+                canonical_article_id: canonical.id.clone(),
+                sources,
+                article_count: members.len(),
+                first_published: canonical.published_at,
+                last_published: members[members.len() - 1].published_at,
+                articles: members,
+            }
+        })
+        .collect();
+
+    stories.sort_by(|a, b| {
+        b.article_count
+            .cmp(&a.article_count)
+            .then_with(|| b.last_published.cmp(&a.last_published))
+    });
+    stories
+}
+
+/// Lowercased word shingles with punctuation and stopwords removed.
+fn shingles(text: &str) -> Vec<String> {
+    let words: Vec<String> = text
+        .split(|c: char| !c.is_alphanumeric())
+        .map(str::to_lowercase)
+        .filter(|w| !w.is_empty() && !STOPWORDS.contains(&w.as_str()))
+        .collect();
+    if words.len() < SHINGLE_SIZE {
+        return words;
+    }
+    words.windows(SHINGLE_SIZE).map(|w| w.join(" ")).collect()
+}
+
+/// MinHash signature: for each seeded hash function, the minimum hash of
+/// any shingle. `None` without shingles, which would otherwise all share one
+/// signature.
+fn signature(shingles: &[String]) -> Option<Vec<u64>> {
+    let hashes: Vec<u64> = shingles.iter().map(|s| fnv1a(s.as_bytes())).collect();
+    (0..SIGNATURE_LEN as u64)
+        .map(|seed| hashes.iter().map(|&h| mix(h ^ mix(seed))).min())
+        .collect()
+}
+
+/// Fraction of signature positions that agree, an estimate of Jaccard similarity.
+fn similarity(a: &[u64], b: &[u64]) -> f64 {
+    let matching = a.iter().zip(b).filter(|(x, y)| x == y).count();
+    matching as f64 / SIGNATURE_LEN as f64
+}
+
+fn find(parent: &mut [usize], mut i: usize) -> usize {
+    while parent[i] != i {
+        parent[i] = parent[parent[i]];
+        i = parent[i];
+    }
+    i
+}
+
+/// SplitMix64 finalizer, used to derive independent hash functions.
+fn mix(mut x: u64) -> u64 {
+    x = x.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    x ^ (x >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock_data::news;
+
+    fn article(
+        id: &str,
+        source: &str,
+        published_at: &str,
+        title: &str,
+        summary: &str,
+    ) -> NewsArticle {
+        NewsArticle {
+            id: id.to_string(),
+            title: title.to_string(),
+            source: source.to_string(),
+            published_at: published_at.parse().unwrap(),
+            category: "Technology".to_string(),
+            summary: summary.to_string(),
+            url: String::new(),
+            author: String::new(),
+            word_count: 100,
+            read_time_minutes: 1,
+            image_url: String::new(),
+            keywords: Vec::new(),
+            entities: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn estimates_jaccard_similarity_of_shingles() {
+        let text = "Leaders reached a consensus on AI safety protocols at the Geneva summit";
+        assert_eq!(shingles("The cat, the HAT!"), vec!["cat hat"]);
+        let a = signature(&shingles(text)).unwrap();
+        assert_eq!(similarity(&a, &a), 1.0);
+        let reworded = signature(&shingles(
+            "Leaders reached a consensus on AI safety protocols at the Geneva meeting",
+        ))
+        .unwrap();
+        assert!(similarity(&a, &reworded) > 0.6);
+        let unrelated = signature(&shingles(
+            "Bitcoin climbs past 100,000 as ETF inflows accelerate",
+        ))
+        .unwrap();
+        assert!(similarity(&a, &unrelated) < 0.1);
+        assert_eq!(signature(&shingles("The... and, of it!")), None);
+    }
+
+    #[test]
+    fn articles_without_words_stay_apart() {
+        let articles = vec![
+            article("a", "Reuters", "2026-10-19T08:00:00+00:00", "", ""),
+            article("b", "Reuters", "2026-10-19T08:30:00+00:00", "—", "..."),
+            article("c", "AP", "2026-10-19T09:00:00+00:00", "The", "Of it"),
+        ];
+        let stories = cluster(&articles, DEFAULT_THRESHOLD);
+        assert_eq!(stories.len(), 3);
+        assert!(stories.iter().all(|s| s.article_count == 1));
+    }
+
+    #[test]
+    fn clusters_near_duplicates_transitively() {
+        let articles = vec![
+            article(
+                "c",
+                "Euronews",
+                "2026-10-19T09:00:00+00:00",
+                "AI Summit in Geneva Concludes with Landmark Safety Agreement",
+                "Leaders agreed binding AI safety guidelines.",
+            ),
+            article(
+                "a",
+                "Reuters",
+                "2026-10-19T08:00:00+00:00",
+                "Global AI Summit Concludes with Landmark Safety Agreement",
+                "World leaders agreed binding AI safety guidelines.",
+            ),
+            article(
+                "x",
+                "CoinDesk",
+                "2026-10-19T10:00:00+00:00",
+                "Bitcoin Climbs Past $100,000",
+                "Spot ETF inflows accelerate.",
+            ),
+            article(
+                "b",
+                "Reuters",
+                "2026-10-19T08:30:00+00:00",
+                "Global AI Summit Ends with Landmark Safety Agreement",
+                "World leaders agreed binding AI safety guidelines.",
+            ),
+        ];
+        let stories = cluster(&articles, DEFAULT_THRESHOLD);
+        assert_eq!(stories.len(), 2);
+
+        let summit = &stories[0];
+        assert_eq!(summit.article_count, 3);
+        assert_eq!(summit.canonical_article_id, "a");
+        assert_eq!(summit.id, "story-a");
+        assert_eq!(summit.sources, vec!["Reuters", "Euronews"]);
+        assert_eq!(
+            (
+                summit.first_published.to_rfc3339(),
+                summit.last_published.to_rfc3339()
+            ),
+            (
+                "2026-10-19T08:00:00+00:00".to_string(),
+                "2026-10-19T09:00:00+00:00".to_string()
+            )
+        );
+        assert_eq!(stories[1].article_count, 1);
+
+        // A threshold of 1 only joins identical texts.
+        assert_eq!(cluster(&articles, 1.0).len(), 4);
+        assert!(cluster(&[], DEFAULT_THRESHOLD).is_empty());
+    }
+
+    #[test]
+    fn groups_the_mock_feed_summit_coverage() {
+        let feed = news::get_feed();
+        let stories = cluster(&feed, DEFAULT_THRESHOLD);
+        assert_eq!(
+            stories.iter().map(|s| s.article_count).sum::<usize>(),
+            feed.len()
+        );
+        let summit = stories
+            .iter()
+            .find(|s| s.articles.iter().any(|a| a.source == "Euronews"))
+            .unwrap();
+        assert!(summit.sources.contains(&"Associated Press".to_string()));
+        assert!(summit.article_count >= 3);
+    }
+}
@@ -0,0 +1,133 @@
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+
+/// How often labels appear together in the same item, e.g. hashtags in the
+/// same post. Labels are compared exactly; callers normalize case first.
+#[derive(Debug, Clone, Default)]
+pub struct CoOccurrence {
+    occurrences: BTreeMap<String, u64>,
+    /// Keyed by the label pair in sorted order.
+    pairs: BTreeMap<(String, String), u64>,
+}
+
+impl CoOccurrence {
+    /// Count every label and every pair of distinct labels per item.
+    /// Repeats of a label within one item count once.
+    pub fn build<I, L>(items: I) -> Self
+    where
+        I: IntoIterator<Item = L>,
+        L: IntoIterator<Item = String>,
+    {
+        let mut graph = Self::default();
+        for item in items {
+            let labels: BTreeSet<String> = item.into_iter().collect();
+            for (i, a) in labels.iter().enumerate() {
+                *graph.occurrences.entry(a.clone()).or_default() += 1;
+                for b in labels.iter().skip(i + 1) {
+                    *graph.pairs.entry((a.clone(), b.clone())).or_default() += 1;
+                }
+            }
+        }
+        graph
+    }
+
+    /// Items containing `label`.
+    pub fn occurrences(&self, label: &str) -> u64 {
+        self.occurrences.get(label).copied().unwrap_or(0)
+    }
+
+    /// Labels within `depth` hops of `start` over pairs seen together at
+    /// least `min_weight` times, each with its distance; `start` comes first.
+    pub fn reachable(&self, start: &str, depth: usize, min_weight: u64) -> Vec<(String, usize)> {
+        if !self.occurrences.contains_key(start) {
+            return Vec::new();
+        }
+        let mut found = vec![(start.to_string(), 0)];
+        let mut queue = VecDeque::from([(start.to_string(), 0)]);
+        while let Some((label, distance)) = queue.pop_front() {
+            if distance == depth {
+                continue;
+            }
+            for ((a, b), &weight) in &self.pairs {
+                let other = if *a == label {
+                    b
+                } else if *b == label {
+                    a
+                } else {
+                    continue;
+                };
+                if weight >= min_weight && !found.iter().any(|(f, _)| f == other) {
+                    found.push((other.clone(), distance + 1));
+                    queue.push_back((other.clone(), distance + 1));
+                }
+            }
+        }
+        found
+    }
+
+    /// Pairs of `labels` seen together at least `min_weight` times, heaviest
+    /// first.
+    pub fn edges_among(&self, labels: &[String], min_weight: u64) -> Vec<(String, String, u64)> {
+        let mut edges: Vec<(String, String, u64)> = self
+            .pairs
+            .iter()
+            .filter(|((a, b), &weight)| {
+                weight >= min_weight && labels.contains(a) && labels.contains(b)
+            })
+            .map(|((a, b), &weight)| (a.clone(), b.clone(), weight))
+            .collect();
+        edges.sort_by_key(|&(_, _, weight)| Reverse(weight));
+        edges
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph() -> CoOccurrence {
+        let posts = [
+            vec!["a", "b"],
+            vec!["a", "b", "b"],
+            vec!["b", "c"],
+            vec!["b", "c"],
+            vec!["c", "d"],
+            vec!["a", "e"],
+        ];
+        CoOccurrence::build(posts.iter().map(|tags| tags.iter().map(|t| t.to_string())))
+    }
+
+    #[test]
+    fn counts_items_and_pairs() {
+        let graph = graph();
+        assert_eq!(graph.occurrences("b"), 4);
+        assert_eq!(graph.occurrences("z"), 0);
+        let labels: Vec<String> = ["a", "b", "c"].iter().map(|s| s.to_string()).collect();
+        assert_eq!(
+            graph.edges_among(&labels, 1),
+            vec![
+                ("a".to_string(), "b".to_string(), 2),
+                ("b".to_string(), "c".to_string(), 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn walks_heavy_edges_to_depth() {
+        let graph = graph();
+        assert_eq!(
+            graph.reachable("a", 1, 2),
+            vec![("a".to_string(), 0), ("b".to_string(), 1)]
+        );
+        assert_eq!(
+            graph.reachable("a", 2, 2),
+            vec![
+                ("a".to_string(), 0),
+                ("b".to_string(), 1),
+                ("c".to_string(), 2)
+            ]
+        );
+        assert_eq!(graph.reachable("a", 3, 1).len(), 5);
+        assert!(graph.reachable("z", 2, 1).is_empty());
+    }
+}
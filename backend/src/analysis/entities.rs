@@ -0,0 +1,314 @@
+use std::sync::OnceLock;
+
+use serde::{Deserialize, Serialize};
+
+use crate::mock_data::news::NewsArticle;
+
+/// Keywords kept per article.
+const MAX_KEYWORDS: usize = 5;
+
+/// Titles that introduce a person's name, e.g. "President Jane Doe".
+const HONORIFICS: &[&str] = &[
+    "Dr",
+    "Prof",
+    "Mr",
+    "Mrs",
+    "Ms",
+    "President",
+    "Chancellor",
+    "Minister",
+    "Senator",
+    "Governor",
+    "CEO",
+    "Chair",
+    "Chairman",
+    "Chairwoman",
+];
+
+const STOPWORDS: &[&str] = &[
+    "about", "across", "after", "against", "ahead", "all", "along", "also", "amid", "and", "are",
+    "around", "as", "at", "before", "behind", "beneath", "between", "but", "by", "can", "could",
+    "despite", "during", "even", "first", "for", "from", "full", "has", "have", "into", "its",
+    "more", "most", "near", "new", "not", "now", "over", "per", "since", "than", "that", "the",
+    "their", "them", "these", "this", "those", "three", "through", "time", "toward", "under",
+    "until", "upon", "was", "were", "which", "while", "who", "will", "with", "within", "without",
+    "year", "years",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EntityKind {
+    Organization,
+    Place,
+    Person,
+    Asset,
+}
+
+/// A named entity mentioned in an article's title or summary.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Entity {
+    pub name: String,
+    pub kind: EntityKind,
+    pub mentions: usize,
+}
+
+/// A known entity and the alternative spellings that refer to it.
+#[derive(Debug, Clone, Deserialize)]
+pub struct LexiconEntry {
+    pub name: String,
+    pub kind: EntityKind,
+    pub aliases: Vec<String>,
+}
+
+/// How often an entity appears across a set of articles.
+#[derive(Debug, Clone, Serialize)]
+pub struct EntitySummary {
+    pub name: String,
+    pub kind: EntityKind,
+    pub article_count: usize,
+    pub mentions: usize,
+}
+
+/// Return the bundled entity lexicon from `lexicon.json`.
+pub fn lexicon() -> &'static [LexiconEntry] {
+    static LEXICON: OnceLock<Vec<LexiconEntry>> = OnceLock::new();
+    LEXICON.get_or_init(|| {
+        serde_json::from_str(include_str!("lexicon.json")).expect("lexicon.json must be valid")
+    })
+}
+
+/// Find the lexicon entry whose name or alias matches `name`, ignoring case,
+/// diacritics on dotted capitals and punctuation.
+pub fn lookup(name: &str) -> Option<&'static LexiconEntry> {
+    let wanted = fold(name);
+    lexicon().iter().find(|entry| {
+        fold(&entry.name) == wanted || entry.aliases.iter().any(|alias| fold(alias) == wanted)
+    })
+}
+
+/// Fill in `keywords` and `entities` from the article's title and summary.
+pub fn enrich(article: &mut NewsArticle) {
+    let title = tokens(&article.title);
+    let summary = tokens(&article.summary);
+
+    let mut entities: Vec<Entity> = Vec::new();
+    let mut candidates: Vec<(String, usize, usize)> = Vec::new();
+    for (weight, words) in [(2, &title), (1, &summary)] {
+        let mut i = 0;
+        while i < words.len() {
+            if let Some((name, kind, len)) = entity_at(words, i) {
+                match entities.iter_mut().find(|e| e.name == name) {
+                    Some(entity) => entity.mentions += 1,
+                    None => entities.push(Entity {
+                        name,
+                        kind,
+                        mentions: 1,
+                    }),
+                }
+                i += len;
+                continue;
+            }
+
+            let word = words[i].to_lowercase();
+            if is_keyword(&word) {
+                match candidates.iter_mut().find(|(w, _, _)| *w == word) {
+                    Some(candidate) => candidate.1 += weight,
+                    None => candidates.push((word, weight, candidates.len())),
+                }
+            }
+            i += 1;
+        }
+    }
+
+    candidates.sort_by(|a, b| b.1.cmp(&a.1).then(a.2.cmp(&b.2)));
+    article.keywords = candidates
+        .into_iter()
+        .take(MAX_KEYWORDS)
+        .map(|(word, _, _)| word)
+        .collect();
+    article.entities = entities;
+}
+
+/// Whether the article mentions the entity called `name` (matched as in [`lookup`]).
+pub fn mentions(article: &NewsArticle, name: &str) -> bool {
+    article.entities.iter().any(|e| mentions_name(e, name))
+}
+
+/// Whether `entity` is the one called `name`, compared as in [`lookup`].
+pub fn mentions_name(entity: &Entity, name: &str) -> bool {
+    fold(&entity.name) == fold(name)
+}
+
+/// Count entity occurrences across `articles`, most widely covered first.
+pub fn summarize(articles: &[NewsArticle]) -> Vec<EntitySummary> {
+    let mut summaries: Vec<EntitySummary> = Vec::new();
+    for entity in articles.iter().flat_map(|a| &a.entities) {
+        match summaries.iter_mut().find(|s| s.name == entity.name) {
+            Some(summary) => {
+                summary.article_count += 1;
+                summary.mentions += entity.mentions;
+            }
+            None => summaries.push(EntitySummary {
+                name: entity.name.clone(),
+                kind: entity.kind,
+                article_count: 1,
+                mentions: entity.mentions,
+            }),
+        }
+    }
+    summaries.sort_by(|a, b| {
+        b.article_count
+            .cmp(&a.article_count)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    summaries
+}
+
+/// Match the longest lexicon name or alias starting at `words[i]`, falling
+/// back to an honorific followed by capitalised words for people.
+fn entity_at(words: &[&str], i: usize) -> Option<(String, EntityKind, usize)> {
+    let mut best: Option<(&LexiconEntry, usize)> = None;
+    for entry in lexicon() {
+        for spelling in std::iter::once(&entry.name).chain(&entry.aliases) {
+            let phrase = tokens(spelling);
+            let len = phrase.len();
+            if len > 0
+                && words.get(i..i + len) == Some(phrase.as_slice())
+                && best.is_none_or(|(_, best_len)| len > best_len)
+            {
+                best = Some((entry, len));
+            }
+        }
+    }
+    if let Some((entry, len)) = best {
+        return Some((entry.name.clone(), entry.kind, len));
+    }
+
+    if !HONORIFICS.contains(&words[i]) {
+        return None;
+    }
+    let name: Vec<&str> = words[i + 1..]
+        .iter()
+        .take(3)
+        .take_while(|w| w.chars().next().is_some_and(char::is_uppercase))
+        .copied()
+        .collect();
+    (!name.is_empty()).then(|| (name.join(" "), EntityKind::Person, name.len() + 1))
+}
+
+fn is_keyword(word: &str) -> bool {
+    word.chars().count() >= 3 && word.chars().any(char::is_alphabetic) && !STOPWORDS.contains(&word)
+}
+
+/// Split text into words, keeping their case; punctuation separates words.
+fn tokens(text: &str) -> Vec<&str> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect()
+}
+
+/// Normalise a name for comparison: lowercase, without the combining dot that
+/// lowercasing "İ" leaves behind, and with punctuation collapsed to spaces.
+fn fold(name: &str) -> String {
+    name.to_lowercase()
+        .chars()
+        .filter(|&c| c != '\u{307}')
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn enriched(title: &str, summary: &str) -> NewsArticle {
+        let mut article = NewsArticle {
+            id: "news-1".to_string(),
+            title: title.to_string(),
+            source: "Wire".to_string(),
+            published_at: "2026-10-19T07:15:00+00:00".parse().unwrap(),
+            category: "Finance".to_string(),
+            summary: summary.to_string(),
+            url: String::new(),
+            author: String::new(),
+            word_count: 100,
+            read_time_minutes: 1,
+            image_url: String::new(),
+            keywords: Vec::new(),
+            entities: Vec::new(),
+        };
+        enrich(&mut article);
+        article
+    }
+
+    fn names(article: &NewsArticle) -> Vec<(&str, EntityKind, usize)> {
+        article
+            .entities
+            .iter()
+            .map(|e| (e.name.as_str(), e.kind, e.mentions))
+            .collect()
+    }
+
+    #[test]
+    fn extracts_entities_under_their_canonical_names() {
+        let article = enriched(
+            "ECB Holds Rates as Bitcoin Slips",
+            "The European Central Bank held rates; BTC fell while President Christine Lagarde spoke in the eurozone.",
+        );
+        assert_eq!(
+            names(&article),
+            vec![
+                ("European Central Bank", EntityKind::Organization, 2),
+                ("Bitcoin", EntityKind::Asset, 2),
+                ("Christine Lagarde", EntityKind::Person, 1),
+                ("Europe", EntityKind::Place, 1),
+            ]
+        );
+        // "rates" scores 2 in the title plus 1 in the summary.
+        assert_eq!(article.keywords[..2], ["rates", "holds"]);
+        assert!(!article.keywords.iter().any(|k| k == "ecb" || k == "the"));
+    }
+
+    #[test]
+    fn prefers_the_longest_match() {
+        let article = enriched("Google DeepMind and Google", "");
+        assert_eq!(
+            names(&article),
+            vec![
+                ("Google DeepMind", EntityKind::Organization, 1),
+                ("Google", EntityKind::Organization, 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn looks_up_aliases_ignoring_case_and_punctuation() {
+        assert_eq!(lookup("btc").unwrap().name, "Bitcoin");
+        assert_eq!(lookup("u.s.").unwrap().name, "United States");
+        assert_eq!(lookup("İSTANBUL").unwrap().name, "Istanbul");
+        assert!(lookup("Atlantis").is_none());
+
+        let article = enriched("Bitcoin rallies", "");
+        assert!(mentions(&article, "BITCOIN"));
+        assert!(!mentions(&article, "Ethereum"));
+    }
+
+    #[test]
+    fn summarizes_by_article_coverage() {
+        let articles = [
+            enriched("Bitcoin and Ethereum rally", "Bitcoin leads."),
+            enriched("Bitcoin slips", ""),
+        ];
+        let summary = summarize(&articles);
+        assert_eq!(
+            summary
+                .iter()
+                .map(|s| (s.name.as_str(), s.article_count, s.mentions))
+                .collect::<Vec<_>>(),
+            vec![("Bitcoin", 2, 3), ("Ethereum", 1, 1)]
+        );
+    }
+}
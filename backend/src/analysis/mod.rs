@@ -0,0 +1,25 @@
+pub mod alerts;
+pub mod clustering;
+pub mod cooccurrence;
+pub mod entities;
+pub mod sentiment;
+pub mod trends;
+
+/// 64-bit FNV-1a hash; stable across runs and platforms, unlike `DefaultHasher`.
+pub(crate) fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &b| {
+        (hash ^ b as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Lowercase `s` and join its alphanumeric runs with hyphens, for URLs and IDs.
+pub(crate) fn slug(s: &str) -> String {
+    s.to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '-' })
+        .collect::<String>()
+        .split('-')
+        .filter(|p| !p.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
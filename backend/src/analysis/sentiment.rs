@@ -0,0 +1,289 @@
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use serde::{Deserialize, Serialize};
+
+use crate::mock_data::social::SentimentBreakdown;
+
+/// Multiplier applied to a word's valence when it is negated.
+const NEGATION_SCALAR: f64 = -0.74;
+
+/// Added to the magnitude of a lexicon word written in capitals for emphasis.
+const CAPS_BOOST: f64 = 0.733;
+
+/// Added per exclamation mark, up to `MAX_EXCLAMATIONS`.
+const EXCLAMATION_BOOST: f64 = 0.292;
+const MAX_EXCLAMATIONS: usize = 4;
+
+/// Normalisation constant approximating the maximum expected raw score.
+const ALPHA: f64 = 15.0;
+
+/// Compound scores at or beyond this magnitude are labelled positive or negative.
+const LABEL_THRESHOLD: f64 = 0.05;
+
+/// Word lists from `sentiment.json`, covering English and Turkish.
+#[derive(Debug, Deserialize)]
+pub struct Lexicon {
+    /// Valence of whole words, from -4 (most negative) to +4.
+    words: HashMap<String, f64>,
+    /// Turkish roots that also score inflected forms, e.g. "mükemmeldi".
+    stems: HashMap<String, f64>,
+    /// Intensifiers (positive) and dampeners (negative) for the following word.
+    boosters: HashMap<String, f64>,
+    /// Words that flip the valence of the next few words.
+    negations: Vec<String>,
+    /// Words that flip the valence of the word before them, as Turkish "değil".
+    post_negations: Vec<String>,
+    /// Words after which the rest of the text carries more weight.
+    contrasts: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SentimentLabel {
+    Positive,
+    Negative,
+    Neutral,
+}
+
+/// VADER-style scores: `compound` is normalised to -1..1 and the three
+/// proportions sum to 1.
+#[derive(Debug, Clone, Serialize)]
+pub struct SentimentScore {
+    pub compound: f64,
+    pub positive: f64,
+    pub negative: f64,
+    pub neutral: f64,
+    pub label: SentimentLabel,
+}
+
+/// Return the bundled lexicon from `sentiment.json`.
+pub fn lexicon() -> &'static Lexicon {
+    static LEXICON: OnceLock<Lexicon> = OnceLock::new();
+    LEXICON.get_or_init(|| {
+        serde_json::from_str(include_str!("sentiment.json")).expect("sentiment.json must be valid")
+    })
+}
+
+/// Score a piece of English or Turkish text.
+///
+/// Each lexicon word contributes its valence, adjusted for preceding
+/// boosters, capitals, negation within three words (or a following
+/// "değil"), and whether it falls before or after a contrast such as "but".
+pub fn score(text: &str) -> SentimentScore {
+    let lexicon = lexicon();
+    let raw: Vec<&str> = text.split_whitespace().collect();
+    let words: Vec<String> = raw.iter().map(|w| normalize(w)).collect();
+    let shouting = raw.iter().any(|w| is_caps(w)) && raw.iter().any(|w| !is_caps(w));
+
+    let contrast_at = words.iter().rposition(|w| lexicon.contrasts.contains(w));
+
+    let mut valences: Vec<f64> = Vec::new();
+    for (i, word) in words.iter().enumerate() {
+        let Some(mut valence) = valence(lexicon, word) else {
+            if !word.is_empty() && !lexicon.boosters.contains_key(word) {
+                valences.push(0.0);
+            }
+            continue;
+        };
+
+        if shouting && is_caps(raw[i]) {
+            valence += CAPS_BOOST * valence.signum();
+        }
+        for (distance, previous) in words[i.saturating_sub(3)..i].iter().rev().enumerate() {
+            if let Some(boost) = lexicon.boosters.get(previous) {
+                let damping = [1.0, 0.95, 0.9][distance];
+                valence += boost * valence.signum() * damping;
+            }
+        }
+        let negated = words[i.saturating_sub(3)..i]
+            .iter()
+            .any(|w| lexicon.negations.contains(w) || w.ends_with("n't"))
+            || words[i + 1..]
+                .iter()
+                .take(2)
+                .any(|w| lexicon.post_negations.contains(w));
+        if negated {
+            valence *= NEGATION_SCALAR;
+        }
+        match contrast_at {
+            Some(at) if i < at => valence *= 0.5,
+            Some(at) if i > at => valence *= 1.5,
+            _ => {}
+        }
+        valences.push(valence);
+    }
+
+    let mut sum: f64 = valences.iter().sum();
+    if sum != 0.0 {
+        let exclamations = text.matches('!').count().min(MAX_EXCLAMATIONS);
+        sum += exclamations as f64 * EXCLAMATION_BOOST * sum.signum();
+    }
+    let compound = sum / (sum * sum + ALPHA).sqrt();
+
+    // Proportions follow VADER: each sentiment word counts its valence plus one.
+    let positive = valences
+        .iter()
+        .filter(|&&v| v > 0.0)
+        .fold(0.0, |sum, v| sum + v + 1.0);
+    let negative = valences
+        .iter()
+        .filter(|&&v| v < 0.0)
+        .fold(0.0, |sum, v| sum + v.abs() + 1.0);
+    let neutral = valences.iter().filter(|&&v| v == 0.0).count() as f64;
+    let total = positive + negative + neutral;
+    let share = |part: f64| {
+        if total == 0.0 {
+            0.0
+        } else {
+            round3(part / total)
+        }
+    };
+
+    SentimentScore {
+        compound: round3(compound),
+        positive: share(positive),
+        negative: share(negative),
+        neutral: if total == 0.0 { 1.0 } else { share(neutral) },
+        label: label(compound),
+    }
+}
+
+/// Percentages of positive, negative and neutral labels, to one decimal.
+pub fn breakdown(labels: impl IntoIterator<Item = SentimentLabel>) -> SentimentBreakdown {
+    let (mut positive, mut negative, mut neutral) = (0usize, 0usize, 0usize);
+    for label in labels {
+        match label {
+            SentimentLabel::Positive => positive += 1,
+            SentimentLabel::Negative => negative += 1,
+            SentimentLabel::Neutral => neutral += 1,
+        }
+    }
+    let total = positive + negative + neutral;
+    let percent = |count: usize| {
+        if total == 0 {
+            0.0
+        } else {
+            (count as f64 / total as f64 * 1000.0).round() / 10.0
+        }
+    };
+    SentimentBreakdown {
+        positive: percent(positive),
+        negative: percent(negative),
+        neutral: percent(neutral),
+    }
+}
+
+fn label(compound: f64) -> SentimentLabel {
+    if compound >= LABEL_THRESHOLD {
+        SentimentLabel::Positive
+    } else if compound <= -LABEL_THRESHOLD {
+        SentimentLabel::Negative
+    } else {
+        SentimentLabel::Neutral
+    }
+}
+
+/// Look a word up, falling back to the longest Turkish root it starts with.
+fn valence(lexicon: &Lexicon, word: &str) -> Option<f64> {
+    if let Some(&valence) = lexicon.words.get(word) {
+        return Some(valence);
+    }
+    lexicon
+        .stems
+        .iter()
+        .filter(|(stem, _)| word.starts_with(stem.as_str()))
+        .max_by_key(|(stem, _)| stem.len())
+        .map(|(_, &valence)| valence)
+}
+
+/// Lowercase a word and trim surrounding punctuation, keeping emoji and the
+/// apostrophes inside contractions. Lowercasing "İ" leaves a combining dot,
+/// which is dropped so "İYİ" and "iyi" match.
+fn normalize(word: &str) -> String {
+    word.trim_matches(|c: char| c.is_ascii_punctuation() || c == '’')
+        .to_lowercase()
+        .replace('\u{307}', "")
+        .replace('’', "'")
+}
+
+/// Whether a word is written entirely in capitals, e.g. "AMAZING".
+fn is_caps(word: &str) -> bool {
+    let letters: Vec<char> = word.chars().filter(|c| c.is_alphabetic()).collect();
+    letters.len() > 1 && letters.iter().all(|c| c.is_uppercase())
+}
+
+fn round3(value: f64) -> f64 {
+    (value * 1000.0).round() / 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scores_plain_english() {
+        assert_eq!(score("I love this").label, SentimentLabel::Positive);
+        assert_eq!(
+            score("What a terrible mess").label,
+            SentimentLabel::Negative
+        );
+        assert_eq!(
+            score("Reading the report today").label,
+            SentimentLabel::Neutral
+        );
+        assert_eq!(score("Reading the report today").neutral, 1.0);
+    }
+
+    #[test]
+    fn negation_flips_valence() {
+        assert_eq!(score("This is not good").label, SentimentLabel::Negative);
+        assert_eq!(
+            score("Honestly it isn't bad").label,
+            SentimentLabel::Positive
+        );
+        assert_eq!(
+            score("Not happy with it at all").label,
+            SentimentLabel::Negative
+        );
+    }
+
+    #[test]
+    fn boosters_caps_and_exclamations_intensify() {
+        let plain = score("The launch was good").compound;
+        assert!(score("The launch was really good").compound > plain);
+        assert!(score("The launch was GOOD").compound > plain);
+        assert!(score("The launch was good!!").compound > plain);
+        assert!(score("The launch was slightly good").compound < plain);
+    }
+
+    #[test]
+    fn contrast_weights_the_second_clause() {
+        let result = score("It looked promising but it was a failure");
+        assert_eq!(result.label, SentimentLabel::Negative);
+    }
+
+    #[test]
+    fn scores_turkish() {
+        assert_eq!(
+            score("Bu gerçekten çok iyi").label,
+            SentimentLabel::Positive
+        );
+        assert_eq!(score("Hiç iyi değil").label, SentimentLabel::Negative);
+        assert_eq!(
+            score("Kötü değil, bence güzel").label,
+            SentimentLabel::Positive
+        );
+        assert_eq!(score("Final mükemmeldi").label, SentimentLabel::Positive);
+        assert_eq!(score("İYİ").label, SentimentLabel::Positive);
+    }
+
+    #[test]
+    fn breakdown_reports_percentages() {
+        use SentimentLabel::*;
+        let result = breakdown([Positive, Positive, Negative]);
+        assert_eq!(result.positive, 66.7);
+        assert_eq!(result.negative, 33.3);
+        assert_eq!(result.neutral, 0.0);
+    }
+}
@@ -0,0 +1,204 @@
+use chrono::{DateTime, TimeDelta, Utc};
+use serde::Serialize;
+
+/// Hours of history bucketed for each topic.
+pub const WINDOW_HOURS: usize = 72;
+
+/// Hours summed into one rate sample for velocity and anomaly detection.
+const RATE_HOURS: usize = 6;
+
+/// A topic is emerging when its latest rate sample sits at least this many
+/// standard deviations above its earlier samples...
+const EMERGING_Z_SCORE: f64 = 2.0;
+
+/// ...and it drew at least this many mentions in that sample.
+const MIN_EMERGING_MENTIONS: u64 = 5;
+
+/// Mentions in one hour of the window.
+#[derive(Debug, Clone, Serialize)]
+pub struct Bucket {
+    pub start: String,
+    pub mentions: u64,
+}
+
+/// How fast a topic's mention rate is changing.
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct Momentum {
+    /// Mentions per hour over the latest `RATE_HOURS`.
+    pub mentions_per_hour: f64,
+    /// Change in mentions per hour against the previous `RATE_HOURS`.
+    pub velocity: f64,
+    /// Change in velocity against the sample before that.
+    pub acceleration: f64,
+    /// Standard score of the latest sample against the earlier ones.
+    pub z_score: f64,
+    pub emerging: bool,
+}
+
+/// Count timestamps into `WINDOW_HOURS` hourly buckets ending at `end`,
+/// oldest first. Timestamps outside the window are ignored.
+pub fn hourly_counts(
+    timestamps: impl IntoIterator<Item = DateTime<Utc>>,
+    end: DateTime<Utc>,
+) -> Vec<u64> {
+    let start = window_start(end);
+    let mut counts = vec![0u64; WINDOW_HOURS];
+    for timestamp in timestamps {
+        if timestamp < start || timestamp >= end {
+            continue;
+        }
+        counts[(timestamp - start).num_hours() as usize] += 1;
+    }
+    counts
+}
+
+/// Label hourly counts from [`hourly_counts`] with their start times.
+pub fn buckets(counts: &[u64], end: DateTime<Utc>) -> Vec<Bucket> {
+    let start = window_start(end);
+    counts
+        .iter()
+        .enumerate()
+        .map(|(i, &mentions)| Bucket {
+            start: (start + TimeDelta::hours(i as i64)).to_rfc3339(),
+            mentions,
+        })
+        .collect()
+}
+
+/// Start time of the busiest bucket, preferring the most recent on ties.
+pub fn peak(counts: &[u64], end: DateTime<Utc>) -> DateTime<Utc> {
+    let index = (0..counts.len())
+        .max_by_key(|&i| (counts[i], i))
+        .unwrap_or(0);
+    window_start(end) + TimeDelta::hours(index as i64)
+}
+
+/// Start time of the first bucket that reached `fraction` of the busiest one.
+pub fn first_reaching(counts: &[u64], end: DateTime<Utc>, fraction: f64) -> DateTime<Utc> {
+    let busiest = counts.iter().copied().max().unwrap_or(0) as f64;
+    let index = counts
+        .iter()
+        .position(|&count| count > 0 && count as f64 >= busiest * fraction)
+        .unwrap_or(0);
+    window_start(end) + TimeDelta::hours(index as i64)
+}
+
+/// Velocity, acceleration and anomaly score from hourly counts.
+///
+/// The counts are summed into consecutive `RATE_HOURS` samples ending with
+/// the latest hour. The z-score compares the latest sample with the mean
+/// and spread of all earlier ones; when they barely vary, the Poisson
+/// spread `sqrt(mean)` (at least 1) stands in so a quiet history does not
+/// turn every blip into an anomaly.
+pub fn momentum(counts: &[u64]) -> Momentum {
+    let samples: Vec<f64> = counts
+        .rchunks(RATE_HOURS)
+        .filter(|chunk| chunk.len() == RATE_HOURS)
+        .map(|chunk| chunk.iter().sum::<u64>() as f64)
+        .rev()
+        .collect();
+    let Some((&latest, history)) = samples.split_last() else {
+        return Momentum::default();
+    };
+
+    let rate = |sample: f64| sample / RATE_HOURS as f64;
+    let nth_last = |n: usize| samples.len().checked_sub(n + 1).map(|i| samples[i]);
+    let velocity = nth_last(1).map_or(0.0, |previous| rate(latest) - rate(previous));
+    let previous_velocity = nth_last(1)
+        .zip(nth_last(2))
+        .map_or(0.0, |(previous, before)| rate(previous) - rate(before));
+
+    let z_score = if history.is_empty() {
+        0.0
+    } else {
+        let mean = history.iter().sum::<f64>() / history.len() as f64;
+        let variance =
+            history.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / history.len() as f64;
+        let spread = variance.sqrt().max(mean.sqrt()).max(1.0);
+        (latest - mean) / spread
+    };
+
+    Momentum {
+        mentions_per_hour: round2(rate(latest)),
+        velocity: round2(velocity),
+        acceleration: round2(velocity - previous_velocity),
+        z_score: round2(z_score),
+        emerging: z_score >= EMERGING_Z_SCORE
+            && velocity > 0.0
+            && latest >= MIN_EMERGING_MENTIONS as f64,
+    }
+}
+
+fn window_start(end: DateTime<Utc>) -> DateTime<Utc> {
+    end - TimeDelta::hours(WINDOW_HOURS as i64)
+}
+
+fn round2(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn end() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2026-10-19T12:00:00Z")
+            .unwrap()
+            .to_utc()
+    }
+
+    #[test]
+    fn buckets_by_hour_within_window() {
+        let end = end();
+        let counts = hourly_counts(
+            [
+                end - TimeDelta::minutes(1),
+                end - TimeDelta::minutes(59),
+                end - TimeDelta::minutes(61),
+                end - TimeDelta::hours(80),
+                end,
+            ],
+            end,
+        );
+        assert_eq!(counts.len(), WINDOW_HOURS);
+        assert_eq!(counts[WINDOW_HOURS - 1], 2);
+        assert_eq!(counts[WINDOW_HOURS - 2], 1);
+        assert_eq!(counts.iter().sum::<u64>(), 3);
+        assert_eq!(peak(&counts, end), end - TimeDelta::hours(1));
+    }
+
+    #[test]
+    fn flat_series_is_not_emerging() {
+        let momentum = momentum(&[2; WINDOW_HOURS]);
+        assert_eq!(momentum.velocity, 0.0);
+        assert_eq!(momentum.acceleration, 0.0);
+        assert_eq!(momentum.z_score, 0.0);
+        assert!(!momentum.emerging);
+    }
+
+    #[test]
+    fn spike_is_emerging() {
+        let mut counts = vec![1; WINDOW_HOURS];
+        for count in &mut counts[WINDOW_HOURS - 3..] {
+            *count = 8;
+        }
+        let momentum = momentum(&counts);
+        assert_eq!(momentum.mentions_per_hour, 4.5);
+        assert_eq!(momentum.velocity, 3.5);
+        assert_eq!(momentum.acceleration, 3.5);
+        assert!(momentum.z_score > EMERGING_Z_SCORE);
+        assert!(momentum.emerging);
+    }
+
+    #[test]
+    fn decline_has_negative_velocity() {
+        let mut counts = vec![4; WINDOW_HOURS];
+        for count in &mut counts[WINDOW_HOURS - 6..] {
+            *count = 1;
+        }
+        let momentum = momentum(&counts);
+        assert_eq!(momentum.velocity, -3.0);
+        assert!(momentum.z_score < 0.0);
+        assert!(!momentum.emerging);
+    }
+}
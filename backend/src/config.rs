@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 /// Rate limiting configuration for the DataPulse demo API.
 pub struct RateLimitConfig {
     /// Maximum requests per day globally per IP.
@@ -17,3 +19,111 @@ impl Default for RateLimitConfig {
         }
     }
 }
+
+/// Where weather alert thresholds come from and where alerts are sent.
+pub struct AlertConfig {
+    /// JSON file replacing the bundled thresholds.
+    pub thresholds: Option<PathBuf>,
+    /// `http://` URLs that receive every published event as a JSON POST.
+    pub webhooks: Vec<String>,
+}
+
+impl AlertConfig {
+    /// Read `ALERT_THRESHOLDS` and the comma-separated `ALERT_WEBHOOKS`.
+    pub fn from_env() -> Self {
+        Self {
+            thresholds: std::env::var("ALERT_THRESHOLDS").ok().map(PathBuf::from),
+            webhooks: std::env::var("ALERT_WEBHOOKS")
+                .map(|v| {
+                    v.split(',')
+                        .map(|s| s.trim().to_string())
+                        .filter(|s| !s.is_empty())
+                        .collect()
+                })
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// Access to the admin endpoints.
+pub struct AdminConfig {
+    /// Bearer token the admin endpoints require; they are hidden when unset.
+    pub token: Option<String>,
+}
+
+impl AdminConfig {
+    /// Read `ADMIN_TOKEN`, ignoring an empty value.
+    pub fn from_env() -> Self {
+        Self {
+            token: std::env::var("ADMIN_TOKEN")
+                .ok()
+                .filter(|token| !token.is_empty()),
+        }
+    }
+}
+
+/// Directory that default data paths are relative to: `BASE_DIR`, or the
+/// working directory the server was started from.
+fn base_dir() -> PathBuf {
+    std::env::var("BASE_DIR")
+        .map(PathBuf::from)
+        .or_else(|_| std::env::current_dir())
+        .unwrap_or_else(|_| PathBuf::from("."))
+}
+
+/// `relative` under `base_dir`, as a location string.
+fn base_path(relative: &str) -> String {
+    base_dir().join(relative).to_string_lossy().into_owned()
+}
+
+/// Where persisted data lives.
+pub struct StorageConfig {
+    /// Root directory of the embedded time-series store.
+    pub data_dir: PathBuf,
+}
+
+impl StorageConfig {
+    /// Read `DATA_DIR`, defaulting to `data` under `base_dir`.
+    pub fn from_env() -> Self {
+        Self {
+            data_dir: std::env::var("DATA_DIR")
+                .map(PathBuf::from)
+                .unwrap_or_else(|_| base_dir().join("data")),
+        }
+    }
+}
+
+/// Locations of the local data sources read by scraper runs.
+pub struct SourceConfig {
+    /// Directory of saved HTML product pages for the price tracker.
+    pub product_pages_dir: PathBuf,
+    /// RSS/Atom feeds for the news aggregator: file paths or `http://` URLs.
+    pub news_feeds: Vec<String>,
+    /// NDJSON exports of social posts for the social media trends scraper.
+    pub social_posts: Vec<String>,
+}
+
+impl SourceConfig {
+    /// Build the source configuration, letting environment variables override
+    /// the defaults (which point at the bundled fixtures under `base_dir`).
+    pub fn from_env() -> Self {
+        Self {
+            product_pages_dir: std::env::var("PRODUCT_PAGES_DIR")
+                .map(PathBuf::from)
+                .unwrap_or_else(|_| base_dir().join("fixtures/product_pages")),
+            news_feeds: std::env::var("NEWS_FEEDS")
+                .map(|v| v.split(',').map(|s| s.trim().to_string()).collect())
+                .unwrap_or_else(|_| {
+                    [
+                        "fixtures/feeds/techwire.rss",
+                        "fixtures/feeds/sciencedaily.atom",
+                    ]
+                    .map(base_path)
+                    .into()
+                }),
+            social_posts: std::env::var("SOCIAL_POSTS")
+                .map(|v| v.split(',').map(|s| s.trim().to_string()).collect())
+                .unwrap_or_else(|_| vec![base_path("fixtures/social/posts.ndjson")]),
+        }
+    }
+}
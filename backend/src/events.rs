@@ -0,0 +1,136 @@
+use std::time::{Duration, Instant};
+
+use chrono::Utc;
+use dashmap::DashMap;
+use serde::Serialize;
+use tokio::sync::broadcast;
+
+use crate::http_client;
+
+/// Events buffered for subscribers that fall behind.
+const CHANNEL_CAPACITY: usize = 256;
+
+/// How long an event id is remembered to avoid publishing it twice.
+const DEDUPE_WINDOW: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Something that happened, sent to `/api/events` subscribers and webhooks.
+#[derive(Debug, Clone, Serialize)]
+pub struct Event {
+    pub id: String,
+    /// Dotted event type, e.g. `weather.alert`.
+    pub kind: String,
+    pub published_at: String,
+    pub data: serde_json::Value,
+}
+
+impl Event {
+    pub fn new(kind: &str, id: &str, data: serde_json::Value) -> Self {
+        Self {
+            id: id.to_string(),
+            kind: kind.to_string(),
+            published_at: Utc::now().to_rfc3339(),
+            data,
+        }
+    }
+}
+
+/// Fans events out to live subscribers and configured webhooks.
+pub struct EventBus {
+    sender: broadcast::Sender<Event>,
+    webhooks: Vec<String>,
+    published: DashMap<String, Instant>,
+}
+
+impl EventBus {
+    pub fn new(webhooks: Vec<String>) -> Self {
+        Self {
+            sender: broadcast::channel(CHANNEL_CAPACITY).0,
+            webhooks,
+            published: DashMap::new(),
+        }
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<Event> {
+        self.sender.subscribe()
+    }
+
+    /// Publish `event` unless one with the same id went out in the last day.
+    /// Webhooks are called in the background; failures are logged, not
+    /// retried. Must be called from within the Tokio runtime.
+    pub fn publish(&self, event: Event) -> bool {
+        let now = Instant::now();
+        self.published
+            .retain(|_, at| now.duration_since(*at) < DEDUPE_WINDOW);
+        if self.published.insert(event.id.clone(), now).is_some() {
+            return false;
+        }
+
+        // No subscribers is not an error; the event is simply not buffered.
+        let _ = self.sender.send(event.clone());
+        if !self.webhooks.is_empty() {
+            let body = serde_json::to_string(&event).unwrap_or_default();
+            for url in self.webhooks.clone() {
+                let body = body.clone();
+                tokio::spawn(async move {
+                    if let Err(err) = http_post(url.clone(), body).await {
+                        println!("Webhook {} failed: {}", url, err);
+                    }
+                });
+            }
+        }
+        true
+    }
+}
+
+/// POST `body` as JSON, failing on anything but a 2xx answer.
+async fn http_post(url: String, body: String) -> Result<(), String> {
+    let response = tokio::task::spawn_blocking(move || {
+        http_client::send("POST", &url, &[("Content-Type", "application/json")], &body)
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(|err| err.to_string())?;
+    if response.is_success() {
+        Ok(())
+    } else {
+        Err(format!("HTTP {}", response.status))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    #[tokio::test]
+    async fn publishes_once_and_calls_webhooks() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let received = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = vec![0; 4096];
+            let n = socket.read(&mut request).await.unwrap();
+            socket
+                .write_all(b"HTTP/1.0 204 No Content\r\n\r\n")
+                .await
+                .unwrap();
+            String::from_utf8_lossy(&request[..n]).to_string()
+        });
+
+        let bus = EventBus::new(vec![format!("http://127.0.0.1:{port}/hooks/alerts")]);
+        let mut subscriber = bus.subscribe();
+        let event = Event::new(
+            "weather.alert",
+            "alert-1",
+            serde_json::json!({ "city": "Oslo" }),
+        );
+        assert!(bus.publish(event.clone()));
+        assert!(!bus.publish(event));
+
+        assert_eq!(subscriber.recv().await.unwrap().id, "alert-1");
+        assert!(subscriber.try_recv().is_err());
+        let request = received.await.unwrap();
+        assert!(request.starts_with("POST /hooks/alerts HTTP/1.0"));
+        assert!(request.contains("\"kind\":\"weather.alert\""));
+    }
+}
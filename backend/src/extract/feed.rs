@@ -0,0 +1,556 @@
+use std::cmp::Reverse;
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+use roxmltree::{Document, Node};
+
+use crate::analysis::entities;
+use crate::http_client::{self, HttpError};
+use crate::mock_data::news::{self, NewsArticle};
+
+const ATOM_NS: &str = "http://www.w3.org/2005/Atom";
+
+/// Summaries longer than this are cut at a word boundary.
+const MAX_SUMMARY_CHARS: usize = 280;
+
+#[derive(Debug)]
+pub enum FeedError {
+    Io(std::io::Error),
+    Http(u16),
+    UnsupportedUrl(String),
+    Xml(roxmltree::Error),
+    UnknownFormat(String),
+}
+
+impl fmt::Display for FeedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "could not read feed: {err}"),
+            Self::Http(status) => write!(f, "feed server answered HTTP {status}"),
+            Self::UnsupportedUrl(url) => write!(f, "unsupported feed location {url}"),
+            Self::Xml(err) => write!(f, "malformed XML: {err}"),
+            Self::UnknownFormat(root) => write!(f, "<{root}> is neither RSS 2.0 nor Atom"),
+        }
+    }
+}
+
+/// A parsed feed document.
+#[derive(Debug, Clone)]
+pub struct Feed {
+    pub title: String,
+    pub articles: Vec<NewsArticle>,
+}
+
+/// The outcome of ingesting one feed location.
+#[derive(Debug)]
+pub struct IngestedFeed {
+    pub location: String,
+    pub result: Result<Feed, FeedError>,
+}
+
+/// Read a feed from a file path or a plain `http://` URL.
+pub fn load(location: &str) -> Result<String, FeedError> {
+    if location.starts_with("http://") {
+        http_get(location)
+    } else if location.contains("://") {
+        Err(FeedError::UnsupportedUrl(location.to_string()))
+    } else {
+        std::fs::read_to_string(location).map_err(FeedError::Io)
+    }
+}
+
+/// Load and parse every feed location as seen at `seen_at`, returning each
+/// one's outcome in order.
+pub fn ingest(locations: &[String], seen_at: DateTime<Utc>) -> Vec<IngestedFeed> {
+    locations
+        .iter()
+        .map(|location| IngestedFeed {
+            location: location.clone(),
+            result: load(location).and_then(|xml| parse_feed(&xml, seen_at)),
+        })
+        .collect()
+}
+
+/// Parse an RSS 2.0 or Atom document into articles, newest first. Items
+/// without a date of their own take the feed's, or else `seen_at`; callers
+/// keep the copy of an article they saw first, so its time does not move.
+pub fn parse_feed(xml: &str, seen_at: DateTime<Utc>) -> Result<Feed, FeedError> {
+    let doc = Document::parse(xml).map_err(FeedError::Xml)?;
+    let root = doc.root_element();
+
+    let mut feed = match root.tag_name().name() {
+        "rss" => {
+            let channel = child(root, "channel")
+                .ok_or_else(|| FeedError::UnknownFormat("rss without channel".into()))?;
+            parse_rss(channel, seen_at)
+        }
+        "feed" if root.tag_name().namespace() == Some(ATOM_NS) => parse_atom(root, seen_at),
+        other => return Err(FeedError::UnknownFormat(other.to_string())),
+    };
+
+    feed.articles.sort_by_key(|a| Reverse(a.published_at));
+    for article in &mut feed.articles {
+        article.id = news::article_id(&article.source, &article.title, &article.url);
+    }
+    Ok(feed)
+}
+
+fn parse_rss(channel: Node, seen_at: DateTime<Utc>) -> Feed {
+    let title = child_text(channel, "title").unwrap_or_else(|| "Unknown Source".to_string());
+    let feed_date = child_text(channel, "lastBuildDate")
+        .or_else(|| child_text(channel, "pubDate"))
+        .and_then(|d| parse_date(&d))
+        .unwrap_or(seen_at);
+
+    let articles = channel
+        .children()
+        .filter(|n| n.has_tag_name("item"))
+        .map(|item| {
+            let description = child_text(item, "description").unwrap_or_default();
+            let body = child_text(item, "encoded").unwrap_or_else(|| description.clone());
+            let author = child_text(item, "creator")
+                .or_else(|| child_text(item, "author").map(|a| normalize_author(&a)))
+                .unwrap_or_else(|| title.clone());
+            let image_url = item
+                .children()
+                .find(|n| {
+                    (n.has_tag_name("enclosure")
+                        && n.attribute("type").is_some_and(|t| t.starts_with("image/")))
+                        || n.has_tag_name("thumbnail")
+                        || (n.has_tag_name("content")
+                            && n.attribute("medium").is_some_and(|m| m == "image"))
+                })
+                .and_then(|n| n.attribute("url"))
+                .unwrap_or_default();
+
+            article(
+                &title,
+                child_text(item, "title").unwrap_or_default(),
+                child_text(item, "link").unwrap_or_default(),
+                child_text(item, "pubDate")
+                    .and_then(|d| parse_date(&d))
+                    .unwrap_or(feed_date),
+                child_text(item, "category"),
+                author,
+                &description,
+                &body,
+                image_url,
+            )
+        })
+        .collect();
+
+    Feed { title, articles }
+}
+
+fn parse_atom(feed: Node, seen_at: DateTime<Utc>) -> Feed {
+    let title = child_text(feed, "title").unwrap_or_else(|| "Unknown Source".to_string());
+    let feed_author = child(feed, "author").and_then(|a| child_text(a, "name"));
+    let feed_date = child_text(feed, "updated")
+        .and_then(|d| parse_date(&d))
+        .unwrap_or(seen_at);
+
+    let articles = feed
+        .children()
+        .filter(|n| n.has_tag_name((ATOM_NS, "entry")))
+        .map(|entry| {
+            let summary = child_text(entry, "summary").unwrap_or_default();
+            let body = child_text(entry, "content").unwrap_or_else(|| summary.clone());
+            let summary = if summary.is_empty() {
+                body.clone()
+            } else {
+                summary
+            };
+            let link = |rel: &str| {
+                entry
+                    .children()
+                    .filter(|n| n.has_tag_name((ATOM_NS, "link")))
+                    .find(|n| n.attribute("rel").unwrap_or("alternate") == rel)
+                    .and_then(|n| n.attribute("href"))
+            };
+
+            article(
+                &title,
+                child_text(entry, "title").unwrap_or_default(),
+                link("alternate").unwrap_or_default().to_string(),
+                child_text(entry, "published")
+                    .or_else(|| child_text(entry, "updated"))
+                    .and_then(|d| parse_date(&d))
+                    .unwrap_or(feed_date),
+                child(entry, "category")
+                    .and_then(|c| c.attribute("label").or(c.attribute("term")))
+                    .map(str::to_string),
+                child(entry, "author")
+                    .and_then(|a| child_text(a, "name"))
+                    .or_else(|| feed_author.clone())
+                    .unwrap_or_else(|| title.clone()),
+                &summary,
+                &body,
+                link("enclosure").unwrap_or_default(),
+            )
+        })
+        .collect();
+
+    Feed { title, articles }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn article(
+    source: &str,
+    title: String,
+    url: String,
+    published: DateTime<Utc>,
+    category: Option<String>,
+    author: String,
+    summary: &str,
+    body: &str,
+    image_url: &str,
+) -> NewsArticle {
+    let word_count = strip_html(body).split_whitespace().count() as u32;
+
+    let mut article = NewsArticle {
+        id: String::new(),
+        title: strip_html(&title),
+        source: source.to_string(),
+        published_at: published,
+        category: category
+            .map(|c| strip_html(&c))
+            .filter(|c| !c.is_empty())
+            .unwrap_or_else(|| "General".to_string()),
+        summary: truncate_words(&strip_html(summary), MAX_SUMMARY_CHARS),
+        url,
+        author: strip_html(&author),
+        word_count,
+        read_time_minutes: news::read_time_minutes(word_count),
+        image_url: image_url.to_string(),
+        keywords: Vec::new(),
+        entities: Vec::new(),
+    };
+    entities::enrich(&mut article);
+    article
+}
+
+/// Parse an RFC 2822 (RSS) or RFC 3339 (Atom) date into UTC.
+fn parse_date(raw: &str) -> Option<DateTime<Utc>> {
+    let raw = raw.trim();
+    DateTime::parse_from_rfc2822(raw)
+        .or_else(|_| DateTime::parse_from_rfc3339(raw))
+        .ok()
+        .map(|d| d.with_timezone(&Utc))
+}
+
+/// Turn an RSS `<author>` such as "news@example.com (Jane Doe)" into "Jane Doe".
+fn normalize_author(raw: &str) -> String {
+    match (raw.find('('), raw.rfind(')')) {
+        (Some(open), Some(close)) if open < close => raw[open + 1..close].trim().to_string(),
+        _ => raw.trim().to_string(),
+    }
+}
+
+/// Remove markup, decode HTML entities and collapse whitespace.
+pub fn strip_html(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => {
+                in_tag = false;
+                text.push(' ');
+            }
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    decode_entities(&text)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn decode_entities(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let decoded = rest.find(';').filter(|&end| end <= 10).and_then(|end| {
+            let entity = &rest[1..end];
+            let c = match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some(' '),
+                "ndash" => Some('–'),
+                "mdash" => Some('—'),
+                "hellip" => Some('…'),
+                "lsquo" => Some('‘'),
+                "rsquo" => Some('’'),
+                "ldquo" => Some('“'),
+                "rdquo" => Some('”'),
+                _ => entity
+                    .strip_prefix("#x")
+                    .or_else(|| entity.strip_prefix("#X"))
+                    .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                    .or_else(|| entity.strip_prefix('#').and_then(|d| d.parse().ok()))
+                    .and_then(char::from_u32),
+            };
+            c.map(|c| (c, end))
+        });
+        match decoded {
+            Some((c, end)) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+fn truncate_words(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let cut: String = text.chars().take(max_chars).collect();
+    let cut = cut.rsplit_once(' ').map(|(head, _)| head).unwrap_or(&cut);
+    format!("{}…", cut.trim_end_matches([',', '.', ';', ':']))
+}
+
+fn child<'a, 'input>(node: Node<'a, 'input>, name: &str) -> Option<Node<'a, 'input>> {
+    node.children()
+        .find(|n| n.is_element() && n.tag_name().name() == name)
+}
+
+/// All text beneath the named child, so CDATA and mixed content are included.
+fn child_text(node: Node, name: &str) -> Option<String> {
+    let text: String = child(node, name)?
+        .descendants()
+        .filter(|n| n.is_text())
+        .filter_map(|n| n.text())
+        .collect();
+    let text = text.trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+/// GET a feed over plain HTTP.
+fn http_get(url: &str) -> Result<String, FeedError> {
+    let accept = (
+        "Accept",
+        "application/rss+xml, application/atom+xml, application/xml",
+    );
+    let response = http_client::send("GET", url, &[accept], "").map_err(|err| match err {
+        HttpError::Io(err) => FeedError::Io(err),
+        HttpError::UnsupportedUrl(url) => FeedError::UnsupportedUrl(url),
+    })?;
+    if !response.is_success() {
+        return Err(FeedError::Http(response.status));
+    }
+    Ok(response.body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+
+    const RSS: &str = include_str!("../../fixtures/feeds/techwire.rss");
+    const ATOM: &str = include_str!("../../fixtures/feeds/sciencedaily.atom");
+
+    fn seen() -> DateTime<Utc> {
+        "2026-10-19T12:00:00Z".parse().unwrap()
+    }
+
+    #[test]
+    fn parses_rss_items() {
+        let feed = parse_feed(RSS, seen()).unwrap();
+        assert_eq!(feed.title, "TechWire");
+        assert_eq!(feed.articles.len(), 3);
+
+        let first = &feed.articles[0];
+        assert_eq!(
+            first.id,
+            news::article_id("TechWire", &first.title, &first.url)
+        );
+        assert_eq!(
+            first.title,
+            "Chipmakers Report Record Quarter as AI Demand Soars"
+        );
+        assert_eq!(first.source, "TechWire");
+        assert_eq!(first.author, "Marcus Rivera");
+        assert_eq!(first.category, "Technology");
+        assert_eq!(first.published_at.to_rfc3339(), "2026-10-19T07:15:00+00:00");
+        assert_eq!(
+            first.image_url,
+            "https://techwire.example.com/img/chips.jpg"
+        );
+        assert_eq!(
+            first.summary,
+            "Leading foundries posted record revenue on surging demand for AI accelerators \
+             & data-centre chips. Analysts expect the trend to continue into 2027."
+        );
+    }
+
+    #[test]
+    fn normalizes_rss_dates_authors_and_escaped_html() {
+        let feed = parse_feed(RSS, seen()).unwrap();
+
+        let browser = &feed.articles[1];
+        assert_eq!(
+            browser.published_at.to_rfc3339(),
+            "2026-10-18T19:40:00+00:00"
+        );
+        assert_eq!(browser.author, "Alex Kowalski");
+        assert_eq!(
+            browser.image_url,
+            "https://techwire.example.com/img/browser.png"
+        );
+        assert!(browser.summary.contains("devices — a milestone"));
+
+        let inquiry = &feed.articles[2];
+        assert_eq!(
+            inquiry.published_at.to_rfc3339(),
+            "2026-10-17T18:05:00+00:00"
+        );
+        assert_eq!(inquiry.author, "TechWire");
+        assert_eq!(inquiry.category, "General");
+        assert!(!inquiry.summary.contains('<'));
+    }
+
+    #[test]
+    fn undated_items_take_the_feed_date_or_when_they_were_seen() {
+        let item = "<item><title>No date</title><link>https://wire.example/a</link></item>";
+        let dated = format!(
+            "<rss><channel><title>Wire</title>\
+             <lastBuildDate>Sun, 18 Oct 2026 21:00:00 +0000</lastBuildDate>{item}</channel></rss>"
+        );
+        let undated = format!("<rss><channel><title>Wire</title>{item}</channel></rss>");
+        let from_feed = parse_feed(&dated, seen()).unwrap().articles.remove(0);
+        assert_eq!(
+            from_feed.published_at.to_rfc3339(),
+            "2026-10-18T21:00:00+00:00"
+        );
+
+        let first = parse_feed(&undated, seen()).unwrap().articles.remove(0);
+        let later = seen() + chrono::TimeDelta::hours(1);
+        let again = parse_feed(&undated, later).unwrap().articles.remove(0);
+        assert_eq!((first.published_at, again.published_at), (seen(), later));
+        // Same story, so the copy kept from the first sighting wins.
+        assert_eq!(first.id, again.id);
+    }
+
+    #[test]
+    fn parses_atom_entries() {
+        let feed = parse_feed(ATOM, seen()).unwrap();
+        assert_eq!(feed.title, "Science Daily Digest");
+        assert_eq!(feed.articles.len(), 2);
+
+        let survey = &feed.articles[0];
+        assert_eq!(
+            survey.published_at.to_rfc3339(),
+            "2026-10-19T02:20:00+00:00"
+        );
+        assert_eq!(survey.author, "Dr. Claire Bennett");
+        assert_eq!(survey.category, "Science");
+        assert_eq!(
+            survey.url,
+            "https://science.example.org/2026/10/deep-sea-survey"
+        );
+        assert_eq!(
+            survey.image_url,
+            "https://science.example.org/img/deep-sea.jpg"
+        );
+        assert!(survey
+            .summary
+            .starts_with("An expedition to the Clarion-Clipperton zone"));
+
+        let crops = &feed.articles[1];
+        assert_eq!(crops.title, "Heat Waves & Crop Yields: A New Model");
+        assert_eq!(crops.author, "Science Daily Desk");
+        assert_eq!(crops.published_at.to_rfc3339(), "2026-10-18T16:45:00+00:00");
+        assert!(crops.summary.ends_with("in 11 of 12 regions."));
+    }
+
+    #[test]
+    fn rejects_unknown_documents() {
+        assert!(matches!(
+            parse_feed("<html><body/></html>", seen()),
+            Err(FeedError::UnknownFormat(_))
+        ));
+        assert!(matches!(
+            parse_feed("<rss><channel>", seen()),
+            Err(FeedError::Xml(_))
+        ));
+    }
+
+    #[test]
+    fn strips_markup_and_entities() {
+        assert_eq!(
+            strip_html("<p>Fish &amp; chips</p><p>&#8220;Tasty&#x201D;&nbsp;!</p>"),
+            "Fish & chips “Tasty” !"
+        );
+        assert_eq!(strip_html("AT&T rocks"), "AT&T rocks");
+    }
+
+    #[test]
+    fn truncates_long_summaries_at_word_boundary() {
+        let long = "word ".repeat(100);
+        let summary = truncate_words(long.trim(), MAX_SUMMARY_CHARS);
+        assert!(summary.chars().count() <= MAX_SUMMARY_CHARS + 1);
+        assert!(summary.ends_with("word…"));
+    }
+
+    #[test]
+    fn ingests_from_local_http_stand_in() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        std::thread::spawn(move || {
+            for (body, stream) in [ATOM, ""].into_iter().zip(listener.incoming()) {
+                let mut stream = stream.unwrap();
+                let mut request = [0u8; 1024];
+                let _ = stream.read(&mut request);
+                let status = if body.is_empty() {
+                    "404 Not Found"
+                } else {
+                    "200 OK"
+                };
+                let _ = write!(
+                    stream,
+                    "HTTP/1.0 {status}\r\nContent-Type: application/atom+xml\r\n\r\n{body}"
+                );
+            }
+        });
+
+        let results = ingest(
+            &[
+                format!("http://127.0.0.1:{port}/science.atom"),
+                format!("http://127.0.0.1:{port}/missing.rss"),
+                "https://example.com/feed".to_string(),
+            ],
+            seen(),
+        );
+        assert_eq!(results[0].result.as_ref().unwrap().articles.len(), 2);
+        assert!(matches!(results[1].result, Err(FeedError::Http(404))));
+        assert!(matches!(
+            results[2].result,
+            Err(FeedError::UnsupportedUrl(_))
+        ));
+    }
+
+    #[test]
+    fn ingests_fixture_files() {
+        let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/feeds");
+        let results = ingest(
+            &[format!("{dir}/techwire.rss"), format!("{dir}/missing.rss")],
+            seen(),
+        );
+        assert_eq!(results[0].result.as_ref().unwrap().articles.len(), 3);
+        assert!(matches!(results[1].result, Err(FeedError::Io(_))));
+    }
+}
@@ -0,0 +1,256 @@
+use std::collections::HashSet;
+use std::fmt;
+
+use serde::Deserialize;
+
+use crate::mock_data::social::{Engagement, SocialPost};
+
+#[derive(Debug)]
+pub enum PostsError {
+    Io(std::io::Error),
+    Empty,
+}
+
+impl fmt::Display for PostsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "could not read posts: {err}"),
+            Self::Empty => write!(f, "no valid posts found"),
+        }
+    }
+}
+
+/// A line that could not be turned into a post.
+#[derive(Debug, Clone)]
+pub struct RejectedLine {
+    /// 1-based line number in the source file.
+    pub line: usize,
+    pub reason: String,
+}
+
+/// Posts parsed from one NDJSON document, plus the lines that were skipped.
+#[derive(Debug, Clone)]
+pub struct PostBatch {
+    pub posts: Vec<SocialPost>,
+    pub rejected: Vec<RejectedLine>,
+}
+
+/// The outcome of ingesting one NDJSON file.
+#[derive(Debug)]
+pub struct IngestedPosts {
+    pub location: String,
+    pub result: Result<PostBatch, PostsError>,
+}
+
+/// One exported post as it appears on an NDJSON line. Engagement counts may
+/// be missing from some exports and default to zero.
+#[derive(Debug, Deserialize)]
+struct RawPost {
+    id: String,
+    platform: String,
+    author: String,
+    text: String,
+    timestamp: String,
+    #[serde(default)]
+    engagement: Engagement,
+}
+
+/// Read and parse every NDJSON file, returning each one's outcome in order.
+pub fn ingest(locations: &[String]) -> Vec<IngestedPosts> {
+    locations
+        .iter()
+        .map(|location| IngestedPosts {
+            location: location.clone(),
+            result: std::fs::read_to_string(location)
+                .map_err(PostsError::Io)
+                .and_then(|text| {
+                    let batch = parse_ndjson(&text);
+                    if batch.posts.is_empty() {
+                        Err(PostsError::Empty)
+                    } else {
+                        Ok(batch)
+                    }
+                }),
+        })
+        .collect()
+}
+
+/// Collect the posts from every successfully ingested file, oldest first,
+/// dropping repeated IDs. When an ID appears more than once, the first copy
+/// in ingestion order is kept.
+pub fn merge(ingested: &[IngestedPosts]) -> Vec<SocialPost> {
+    let mut seen = HashSet::new();
+    let mut posts: Vec<SocialPost> = ingested
+        .iter()
+        .filter_map(|i| i.result.as_ref().ok())
+        .flat_map(|batch| batch.posts.iter())
+        .filter(|post| seen.insert(post.id.as_str()))
+        .cloned()
+        .collect();
+    posts.sort_by(|a, b| (a.timestamp, &a.id).cmp(&(b.timestamp, &b.id)));
+    posts
+}
+
+/// Parse newline-delimited JSON posts. Blank lines are ignored; malformed
+/// lines are reported in `rejected` rather than failing the whole file.
+pub fn parse_ndjson(text: &str) -> PostBatch {
+    let mut batch = PostBatch {
+        posts: Vec::new(),
+        rejected: Vec::new(),
+    };
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        match serde_json::from_str::<RawPost>(line)
+            .map_err(|err| err.to_string())
+            .and_then(post)
+        {
+            Ok(post) => batch.posts.push(post),
+            Err(reason) => batch.rejected.push(RejectedLine {
+                line: i + 1,
+                reason,
+            }),
+        }
+    }
+    batch
+}
+
+fn post(raw: RawPost) -> Result<SocialPost, String> {
+    let timestamp = chrono::DateTime::parse_from_rfc3339(raw.timestamp.trim())
+        .map_err(|err| format!("bad timestamp {:?}: {err}", raw.timestamp))?
+        .to_utc();
+    if raw.text.trim().is_empty() {
+        return Err("empty text".to_string());
+    }
+
+    Ok(SocialPost {
+        hashtags: hashtags(&raw.text),
+        mentions: mentions(&raw.text),
+        id: raw.id,
+        platform: normalize_platform(&raw.platform),
+        author: raw.author,
+        text: raw.text,
+        timestamp,
+        engagement: raw.engagement,
+    })
+}
+
+/// Hashtags in the text, in order of first use, e.g. `["#Bitcoin", "#ETF"]`.
+pub fn hashtags(text: &str) -> Vec<String> {
+    tagged_words(text, '#')
+}
+
+/// Accounts mentioned in the text, e.g. `["@SpaceX"]`.
+pub fn mentions(text: &str) -> Vec<String> {
+    tagged_words(text, '@')
+}
+
+/// Words starting with `sigil` followed by letters, digits or underscores.
+/// Repeats differing only in case are kept once, with their first spelling.
+fn tagged_words(text: &str, sigil: char) -> Vec<String> {
+    let mut found: Vec<String> = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find(sigil) {
+        let preceded_by_word = rest[..start]
+            .chars()
+            .next_back()
+            .is_some_and(|c| c.is_alphanumeric() || c == '_');
+        let after = &rest[start + sigil.len_utf8()..];
+        let len = after
+            .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .unwrap_or(after.len());
+        let body = &after[..len];
+        if !preceded_by_word && body.chars().any(char::is_alphabetic) {
+            let tag = format!("{sigil}{body}");
+            if !found.iter().any(|f| f.to_lowercase() == tag.to_lowercase()) {
+                found.push(tag);
+            }
+        }
+        rest = &after[len..];
+    }
+    found
+}
+
+/// Use one spelling per platform so exports from different tools group together.
+pub fn normalize_platform(raw: &str) -> String {
+    match raw.trim().to_lowercase().as_str() {
+        "twitter" | "x" => "Twitter".to_string(),
+        "reddit" => "Reddit".to_string(),
+        "instagram" | "ig" => "Instagram".to_string(),
+        _ => raw.trim().to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POSTS: &str = include_str!("../../fixtures/social/posts.ndjson");
+
+    #[test]
+    fn parses_bundled_export() {
+        let batch = parse_ndjson(POSTS);
+        assert!(batch.rejected.is_empty());
+        assert_eq!(batch.posts.len(), POSTS.lines().count());
+        assert!(batch.posts.iter().all(|p| !p.hashtags.is_empty()));
+    }
+
+    #[test]
+    fn reports_malformed_lines() {
+        let text = "\
+{\"id\":\"tw-1\",\"platform\":\"x\",\"author\":\"@a\",\"text\":\"Hello #Rust\",\"timestamp\":\"2026-10-19T10:00:00Z\"}
+
+not json
+{\"id\":\"tw-2\",\"platform\":\"Twitter\",\"author\":\"@b\",\"text\":\"hi\",\"timestamp\":\"yesterday\"}
+";
+        let batch = parse_ndjson(text);
+        assert_eq!(batch.posts.len(), 1);
+        assert_eq!(batch.posts[0].platform, "Twitter");
+        assert_eq!(batch.posts[0].engagement.likes, 0);
+        let lines: Vec<usize> = batch.rejected.iter().map(|r| r.line).collect();
+        assert_eq!(lines, vec![3, 4]);
+    }
+
+    #[test]
+    fn merges_files_dropping_repeated_ids() {
+        let file = |location: &str, text: &str| IngestedPosts {
+            location: location.to_string(),
+            result: Ok(parse_ndjson(text)),
+        };
+        let line = |id: &str, time: &str| {
+            format!(
+                "{{\"id\":\"{id}\",\"platform\":\"Reddit\",\"author\":\"u/a\",\
+                 \"text\":\"#Rust\",\"timestamp\":\"2026-10-19T{time}:00Z\"}}\n"
+            )
+        };
+        let first = line("rd-1", "10:00") + &line("rd-2", "11:00");
+        // The re-export moved rd-1 to after rd-2, so the copies are not adjacent.
+        let second = line("rd-1", "12:00") + &line("rd-3", "09:00");
+        let ingested = [
+            file("a.ndjson", &first),
+            IngestedPosts {
+                location: "missing.ndjson".to_string(),
+                result: Err(PostsError::Empty),
+            },
+            file("b.ndjson", &second),
+        ];
+
+        let posts = merge(&ingested);
+        let ids: Vec<&str> = posts.iter().map(|p| p.id.as_str()).collect();
+        assert_eq!(ids, vec!["rd-3", "rd-1", "rd-2"]);
+        assert_eq!(posts[1].timestamp.to_rfc3339(), "2026-10-19T10:00:00+00:00");
+    }
+
+    #[test]
+    fn extracts_hashtags_and_mentions() {
+        let text = "@SpaceX launch! #Mars #Starship, #mars again. mail@example.com #2026 #COP31";
+        assert_eq!(hashtags(text), vec!["#Mars", "#Starship", "#COP31"]);
+        assert_eq!(mentions(text), vec!["@SpaceX"]);
+        assert_eq!(
+            hashtags("Şampiyonlar #ŞampiyonlarLigi"),
+            vec!["#ŞampiyonlarLigi"]
+        );
+    }
+}
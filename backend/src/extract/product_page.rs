@@ -0,0 +1,392 @@
+use std::fmt;
+use std::path::Path;
+use std::sync::OnceLock;
+
+use chrono::Utc;
+use scraper::{ElementRef, Html, Selector};
+use serde::Deserialize;
+
+use crate::analysis::slug;
+use crate::mock_data::ecommerce::{Offer, PricePoint, Product};
+
+/// Declarative extraction rules for one retailer's product pages.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SiteRules {
+    pub site: String,
+    pub domain: String,
+    pub id: Option<FieldRule>,
+    pub name: FieldRule,
+    pub category: Option<FieldRule>,
+    pub price: FieldRule,
+    pub rating: Option<FieldRule>,
+    pub review_count: Option<FieldRule>,
+    pub stock: StockRule,
+}
+
+/// A CSS selector, optionally reading an attribute instead of the element text.
+#[derive(Debug, Clone, Deserialize)]
+pub struct FieldRule {
+    pub selector: String,
+    pub attr: Option<String>,
+}
+
+/// A product is in stock when `selector` matches and its text does not
+/// contain `out_of_stock_text` (compared case-insensitively).
+#[derive(Debug, Clone, Deserialize)]
+pub struct StockRule {
+    pub selector: String,
+    pub out_of_stock_text: Option<String>,
+}
+
+#[derive(Debug)]
+pub enum ExtractError {
+    UnknownSite(String),
+    InvalidSelector {
+        field: &'static str,
+        selector: String,
+    },
+    MissingField(&'static str),
+    InvalidPrice(String),
+    Io(std::io::Error),
+}
+
+impl fmt::Display for ExtractError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownSite(url) => write!(f, "no extraction rules match {url}"),
+            Self::InvalidSelector { field, selector } => {
+                write!(f, "invalid selector for {field}: {selector}")
+            }
+            Self::MissingField(field) => write!(f, "no element found for {field}"),
+            Self::InvalidPrice(text) => write!(f, "could not parse price from {text:?}"),
+            Self::Io(err) => write!(f, "could not read page: {err}"),
+        }
+    }
+}
+
+/// Return the bundled per-site rules from `sites.json`.
+pub fn site_rules() -> &'static [SiteRules] {
+    static RULES: OnceLock<Vec<SiteRules>> = OnceLock::new();
+    RULES.get_or_init(|| {
+        serde_json::from_str(include_str!("sites.json")).expect("sites.json must be valid")
+    })
+}
+
+/// Find the rules whose domain matches the host of `url`.
+pub fn rules_for_url(url: &str) -> Option<&'static SiteRules> {
+    let host = url
+        .split("://")
+        .nth(1)
+        .unwrap_or(url)
+        .split(['/', '?', '#'])
+        .next()?;
+    site_rules()
+        .iter()
+        .find(|r| host == r.domain || host.ends_with(&format!(".{}", r.domain)))
+}
+
+/// Extract a product from an HTML page. When `url` is `None` the page's
+/// canonical link is used to pick the site, falling back to trying each
+/// site's rules in turn.
+pub fn extract_product(html: &str, url: Option<&str>) -> Result<Product, ExtractError> {
+    let document = Html::parse_document(html);
+    let url = url.map(str::to_string).or_else(|| canonical_url(&document));
+
+    match url.as_deref() {
+        Some(url) => {
+            let rules = rules_for_url(url).ok_or_else(|| ExtractError::UnknownSite(url.into()))?;
+            extract_with(&document, rules, Some(url))
+        }
+        None => site_rules()
+            .iter()
+            .find_map(|rules| extract_with(&document, rules, None).ok())
+            .ok_or_else(|| ExtractError::UnknownSite("page without canonical URL".into())),
+    }
+}
+
+/// The outcome of extracting one saved page.
+#[derive(Debug)]
+pub struct ExtractedPage {
+    pub file: String,
+    pub result: Result<Product, ExtractError>,
+}
+
+/// Extract every `.html` page in `dir`, in file name order.
+pub fn extract_dir(dir: &Path) -> Result<Vec<ExtractedPage>, ExtractError> {
+    let mut paths: Vec<_> = std::fs::read_dir(dir)
+        .map_err(ExtractError::Io)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "html"))
+        .collect();
+    paths.sort();
+
+    Ok(paths
+        .into_iter()
+        .map(|path| {
+            let file = path
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            let result = std::fs::read_to_string(&path)
+                .map_err(ExtractError::Io)
+                .and_then(|html| extract_product(&html, None));
+            ExtractedPage { file, result }
+        })
+        .collect())
+}
+
+fn extract_with(
+    document: &Html,
+    rules: &SiteRules,
+    url: Option<&str>,
+) -> Result<Product, ExtractError> {
+    let name =
+        select_text(document, "name", &rules.name)?.ok_or(ExtractError::MissingField("name"))?;
+    let price_text =
+        select_text(document, "price", &rules.price)?.ok_or(ExtractError::MissingField("price"))?;
+    let price = parse_price(&price_text).ok_or(ExtractError::InvalidPrice(price_text))?;
+
+    let id = match &rules.id {
+        Some(rule) => select_text(document, "id", rule)?,
+        None => None,
+    }
+    .unwrap_or_else(|| slug(&name));
+    let category = match &rules.category {
+        Some(rule) => select_text(document, "category", rule)?,
+        None => None,
+    }
+    .unwrap_or_else(|| "Uncategorized".to_string());
+    let rating = match &rules.rating {
+        Some(rule) => select_text(document, "rating", rule)?.and_then(|t| parse_decimal(&t)),
+        None => None,
+    };
+    let review_count = match &rules.review_count {
+        Some(rule) => select_text(document, "review_count", rule)?.and_then(|t| parse_count(&t)),
+        None => None,
+    };
+
+    let stock_selector = compile("stock", &rules.stock.selector)?;
+    let in_stock = document.select(&stock_selector).next().is_some_and(|el| {
+        let text = element_text(el).to_lowercase();
+        rules
+            .stock
+            .out_of_stock_text
+            .as_ref()
+            .is_none_or(|marker| !text.contains(&marker.to_lowercase()))
+    });
+
+    let url = url
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("https://{}/p/{}", rules.domain, id));
+
+    Ok(Product {
+        id,
+        name,
+        category,
+        price,
+        currency: "TRY".to_string(),
+        price_history: vec![PricePoint {
+            date: Utc::now().format("%Y-%m-%d").to_string(),
+            price,
+        }],
+        source: rules.site.clone(),
+        rating: rating.unwrap_or(0.0),
+        review_count: review_count.unwrap_or(0),
+        in_stock,
+        url: url.clone(),
+        offers: vec![Offer {
+            source: rules.site.clone(),
+            price,
+            currency: "TRY".to_string(),
+            in_stock,
+            stock_count: None,
+            url,
+        }],
+        best_price: price,
+        best_price_source: rules.site.clone(),
+    })
+}
+
+/// Currency markers that may precede or follow a price.
+const CURRENCY_MARKERS: [&str; 3] = ["TL", "₺", "TRY"];
+
+/// Parse a displayed price such as "42.999,00 TL", "₺5.199,00" or "3.299 TL".
+///
+/// Only one number is read: the last one next to a currency marker, or the
+/// last one if none is, so badges like "%10 indirim" are skipped. A comma is
+/// always the decimal separator; a dot is a thousands separator unless it is
+/// the only separator and is followed by one or two digits.
+pub fn parse_price(text: &str) -> Option<f64> {
+    let mut numbers = Vec::new();
+    let mut start = None;
+    for (i, c) in text.char_indices().chain([(text.len(), ' ')]) {
+        match (c.is_ascii_digit() || c == '.' || c == ',', start) {
+            (true, None) => start = Some(i),
+            (false, Some(first)) => {
+                numbers.push((first, i));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    numbers.retain(|&(first, end)| text[first..end].contains(|c: char| c.is_ascii_digit()));
+    let priced = |first: usize, end: usize| {
+        let (before, after) = (text[..first].trim_end(), text[end..].trim_start());
+        CURRENCY_MARKERS
+            .iter()
+            .any(|marker| before.ends_with(marker) || after.starts_with(marker))
+    };
+    let &(first, end) = numbers
+        .iter()
+        .rev()
+        .find(|&&(first, end)| priced(first, end))
+        .or(numbers.last())?;
+    let digits = text[first..end].trim_matches(|c| c == '.' || c == ',');
+
+    let normalized = if digits.contains(',') {
+        digits.replace('.', "").replace(',', ".")
+    } else {
+        match digits.rsplit_once('.') {
+            Some((head, tail)) if tail.len() <= 2 && !head.contains('.') => digits.to_string(),
+            _ => digits.replace('.', ""),
+        }
+    };
+    normalized.parse().ok()
+}
+
+/// Parse a decimal such as a rating that may use a Turkish decimal comma.
+fn parse_decimal(text: &str) -> Option<f64> {
+    text.trim().replace(',', ".").parse().ok()
+}
+
+/// Parse a count such as "3.421 Değerlendirme" by keeping only its digits.
+fn parse_count(text: &str) -> Option<u32> {
+    let digits: String = text.chars().filter(char::is_ascii_digit).collect();
+    digits.parse().ok()
+}
+
+fn select_text(
+    document: &Html,
+    field: &'static str,
+    rule: &FieldRule,
+) -> Result<Option<String>, ExtractError> {
+    let selector = compile(field, &rule.selector)?;
+    Ok(document
+        .select(&selector)
+        .next()
+        .and_then(|el| match &rule.attr {
+            Some(attr) => el.value().attr(attr).map(|v| v.trim().to_string()),
+            None => Some(element_text(el)),
+        })
+        .filter(|text| !text.is_empty()))
+}
+
+fn compile(field: &'static str, selector: &str) -> Result<Selector, ExtractError> {
+    Selector::parse(selector).map_err(|_| ExtractError::InvalidSelector {
+        field,
+        selector: selector.to_string(),
+    })
+}
+
+/// Element text with runs of whitespace collapsed to single spaces.
+fn element_text(el: ElementRef<'_>) -> String {
+    el.text()
+        .flat_map(str::split_whitespace)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn canonical_url(document: &Html) -> Option<String> {
+    let selector = Selector::parse("link[rel=canonical], meta[property='og:url']").ok()?;
+    document.select(&selector).find_map(|el| {
+        el.value()
+            .attr("href")
+            .or_else(|| el.value().attr("content"))
+            .map(str::to_string)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TRENDYOL: &str = include_str!("../../fixtures/product_pages/trendyol-elec-001.html");
+    const HEPSIBURADA: &str =
+        include_str!("../../fixtures/product_pages/hepsiburada-cloth-002.html");
+    const N11: &str = include_str!("../../fixtures/product_pages/n11-elec-007.html");
+
+    #[test]
+    fn parses_turkish_price_formats() {
+        assert_eq!(parse_price("42.999,00 TL"), Some(42999.0));
+        assert_eq!(parse_price("₺5.199,00"), Some(5199.0));
+        assert_eq!(parse_price("3.299 TL"), Some(3299.0));
+        assert_eq!(parse_price("1.234.567,89 TL"), Some(1234567.89));
+        assert_eq!(parse_price("799,90 TL"), Some(799.9));
+        assert_eq!(parse_price("5199.00"), Some(5199.0));
+        assert_eq!(parse_price("Fiyat yok"), None);
+        assert_eq!(parse_price("%10 indirim 42.999,00 TL"), Some(42999.0));
+        assert_eq!(parse_price("₺5.199,00 (3 taksit)"), Some(5199.0));
+        assert_eq!(parse_price("Sepette 2. ürün 1.499,90"), Some(1499.9));
+    }
+
+    #[test]
+    fn extracts_trendyol_page_via_canonical_link() {
+        let product = extract_product(TRENDYOL, None).unwrap();
+        assert_eq!(product.id, "elec-001");
+        assert_eq!(
+            product.name,
+            "Samsung Galaxy S24 Ultra 256 GB Titanyum Siyah"
+        );
+        assert_eq!(product.category, "Elektronik");
+        assert_eq!(product.source, "Trendyol");
+        assert_eq!(product.price, 42999.0);
+        assert_eq!(product.rating, 4.8);
+        assert_eq!(product.review_count, 3421);
+        assert!(product.in_stock);
+        assert!(product.url.starts_with("https://www.trendyol.com/"));
+        // The page says whether it is in stock, not how many are left.
+        let offer = &product.offers[0];
+        assert_eq!((offer.in_stock, offer.stock_count), (true, None));
+    }
+
+    #[test]
+    fn extracts_hepsiburada_page_with_explicit_url() {
+        let url = "https://www.hepsiburada.com/adidas-ultraboost-23-p-cloth-002";
+        let product = extract_product(HEPSIBURADA, Some(url)).unwrap();
+        assert_eq!(product.id, "cloth-002");
+        assert_eq!(product.name, "Adidas Ultraboost 23 Erkek Koşu Ayakkabısı");
+        assert_eq!(product.category, "Giyim");
+        assert_eq!(product.price, 5199.0);
+        assert_eq!(product.rating, 4.6);
+        assert_eq!(product.review_count, 3210);
+        assert!(product.in_stock);
+        assert_eq!(product.url, url);
+    }
+
+    #[test]
+    fn detects_out_of_stock_without_canonical_link() {
+        let product = extract_product(N11, None).unwrap();
+        assert_eq!(product.id, "elec-007");
+        assert_eq!(product.source, "n11");
+        assert_eq!(product.price, 3299.0);
+        assert_eq!(product.review_count, 4521);
+        assert!(!product.in_stock);
+        assert_eq!(product.url, "https://n11.com/p/elec-007");
+        let offer = &product.offers[0];
+        assert_eq!((offer.in_stock, offer.stock_count), (false, None));
+    }
+
+    #[test]
+    fn rejects_pages_from_unknown_sites() {
+        let err = extract_product(TRENDYOL, Some("https://example.com/p/1")).unwrap_err();
+        assert!(matches!(err, ExtractError::UnknownSite(_)));
+    }
+
+    #[test]
+    fn extracts_every_fixture_in_directory() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures/product_pages");
+        let results = extract_dir(&dir).unwrap();
+        assert_eq!(results.len(), 3);
+        assert!(results.iter().all(|page| page.result.is_ok()));
+    }
+}
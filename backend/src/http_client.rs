@@ -0,0 +1,246 @@
+use std::fmt;
+use std::io::{Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::time::{Duration, Instant};
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Applies to each read and write, not the whole exchange.
+const IO_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The whole exchange, so a server trickling bytes cannot hold a caller.
+const REQUEST_DEADLINE: Duration = Duration::from_secs(30);
+
+/// Largest response, headers included, that is read.
+const MAX_RESPONSE_BYTES: u64 = 8 * 1024 * 1024;
+
+#[derive(Debug)]
+pub enum HttpError {
+    Io(std::io::Error),
+    UnsupportedUrl(String),
+}
+
+impl fmt::Display for HttpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "{err}"),
+            Self::UnsupportedUrl(url) => write!(f, "unsupported URL {url}"),
+        }
+    }
+}
+
+/// A response's status code and body.
+#[derive(Debug)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub body: String,
+}
+
+impl HttpResponse {
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+}
+
+/// Minimal blocking HTTP/1.0 exchange with a plain `http://` URL, enough for
+/// reading feeds from a local stand-in server and delivering webhooks.
+/// `headers` are sent after `Host` and `User-Agent`; a non-empty `body` gets
+/// a `Content-Length`. Fails with a timeout once `REQUEST_DEADLINE` has
+/// passed and with invalid data past `MAX_RESPONSE_BYTES`.
+pub fn send(
+    method: &str,
+    url: &str,
+    headers: &[(&str, &str)],
+    body: &str,
+) -> Result<HttpResponse, HttpError> {
+    exchange(method, url, headers, body, REQUEST_DEADLINE)
+}
+
+fn exchange(
+    method: &str,
+    url: &str,
+    headers: &[(&str, &str)],
+    body: &str,
+    deadline: Duration,
+) -> Result<HttpResponse, HttpError> {
+    let started = Instant::now();
+    let rest = url
+        .strip_prefix("http://")
+        .ok_or_else(|| HttpError::UnsupportedUrl(url.to_string()))?;
+    let (authority, path) = match rest.find('/') {
+        Some(i) => (&rest[..i], &rest[i..]),
+        None => (rest, "/"),
+    };
+    // `Host` carries the port unless it is the default one.
+    let (host, addr) = match authority.split_once(':') {
+        Some((name, "80")) => (name, authority.to_string()),
+        Some(_) => (authority, authority.to_string()),
+        None => (authority, format!("{authority}:80")),
+    };
+
+    let addr = addr
+        .to_socket_addrs()
+        .map_err(HttpError::Io)?
+        .next()
+        .ok_or_else(|| HttpError::UnsupportedUrl(url.to_string()))?;
+    let mut stream =
+        TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT.min(deadline)).map_err(HttpError::Io)?;
+    // Each read and write may take what is left of the deadline, at most.
+    let remaining = || {
+        deadline
+            .checked_sub(started.elapsed())
+            .filter(|left| !left.is_zero())
+            .map(|left| left.min(IO_TIMEOUT))
+            .ok_or_else(|| {
+                HttpError::Io(std::io::Error::new(
+                    std::io::ErrorKind::TimedOut,
+                    "request deadline passed",
+                ))
+            })
+    };
+    stream
+        .set_write_timeout(Some(remaining()?))
+        .map_err(HttpError::Io)?;
+
+    let mut request =
+        format!("{method} {path} HTTP/1.0\r\nHost: {host}\r\nUser-Agent: DataPulse/0.1\r\n");
+    for (name, value) in headers {
+        request.push_str(&format!("{name}: {value}\r\n"));
+    }
+    if !body.is_empty() {
+        request.push_str(&format!("Content-Length: {}\r\n", body.len()));
+    }
+    request.push_str("\r\n");
+    request.push_str(body);
+    stream
+        .write_all(request.as_bytes())
+        .map_err(HttpError::Io)?;
+
+    let mut response = Vec::new();
+    let mut limited = (&stream).take(MAX_RESPONSE_BYTES + 1);
+    let mut chunk = [0u8; 8192];
+    loop {
+        stream
+            .set_read_timeout(Some(remaining()?))
+            .map_err(HttpError::Io)?;
+        match limited.read(&mut chunk) {
+            Ok(0) => break,
+            Ok(n) => response.extend_from_slice(&chunk[..n]),
+            Err(err) if err.kind() == std::io::ErrorKind::Interrupted => {}
+            Err(err) => return Err(HttpError::Io(err)),
+        }
+    }
+    if response.len() as u64 > MAX_RESPONSE_BYTES {
+        return Err(HttpError::Io(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("response exceeds {MAX_RESPONSE_BYTES} bytes"),
+        )));
+    }
+    let response = String::from_utf8_lossy(&response);
+    let (head, body) = response.split_once("\r\n\r\n").unwrap_or((&response, ""));
+    let status = head
+        .split_whitespace()
+        .nth(1)
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(0);
+    Ok(HttpResponse {
+        status,
+        body: body.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::BufRead;
+    use std::net::TcpListener;
+    use std::thread;
+
+    /// Serve one connection: hand the request head to `respond` and write
+    /// back what it returns, a chunk at a time with `pause` between chunks.
+    fn serve(
+        respond: impl FnOnce(&str) -> Vec<Vec<u8>> + Send + 'static,
+        pause: Duration,
+    ) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = std::io::BufReader::new(&stream);
+            let mut head = String::new();
+            while reader.read_line(&mut head).unwrap() > 2 {}
+            for chunk in respond(&head) {
+                if (&stream).write_all(&chunk).is_err() {
+                    return;
+                }
+                thread::sleep(pause);
+            }
+        });
+        format!("http://{addr}/feed")
+    }
+
+    #[test]
+    fn sends_the_port_in_host_and_reads_the_response() {
+        let url = serve(
+            |head| {
+                let host = head
+                    .lines()
+                    .find_map(|line| line.strip_prefix("Host: "))
+                    .unwrap_or_default()
+                    .to_string();
+                vec![format!("HTTP/1.0 201 Created\r\n\r\n{host}").into_bytes()]
+            },
+            Duration::ZERO,
+        );
+        let response = send("GET", &url, &[], "").unwrap();
+        assert_eq!(response.status, 201);
+        assert!(response.is_success());
+        assert_eq!(
+            format!("http://{}/feed", response.body),
+            url,
+            "Host must carry the non-default port"
+        );
+    }
+
+    #[test]
+    fn rejects_oversized_responses() {
+        let url = serve(
+            |_| {
+                let mut response = b"HTTP/1.0 200 OK\r\n\r\n".to_vec();
+                response.resize(MAX_RESPONSE_BYTES as usize + 1, b'x');
+                vec![response]
+            },
+            Duration::ZERO,
+        );
+        match send("GET", &url, &[], "") {
+            Err(HttpError::Io(err)) => assert_eq!(err.kind(), std::io::ErrorKind::InvalidData),
+            other => panic!("expected an oversized response error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn gives_up_on_a_trickling_server_at_the_deadline() {
+        let url = serve(
+            |_| {
+                std::iter::once(b"HTTP/1.0 200 OK\r\n\r\n".to_vec())
+                    .chain(std::iter::repeat_n(b"x".to_vec(), 100))
+                    .collect()
+            },
+            Duration::from_millis(20),
+        );
+        let started = Instant::now();
+        match exchange("GET", &url, &[], "", Duration::from_millis(200)) {
+            Err(HttpError::Io(err)) => assert_eq!(err.kind(), std::io::ErrorKind::TimedOut),
+            other => panic!("expected a timeout, got {other:?}"),
+        }
+        assert!(started.elapsed() < Duration::from_secs(1));
+    }
+
+    #[test]
+    fn only_plain_http_urls_are_supported() {
+        assert!(matches!(
+            send("GET", "https://example.com/feed", &[], ""),
+            Err(HttpError::UnsupportedUrl(_))
+        ));
+    }
+}
@@ -1,11 +1,18 @@
+mod analysis;
 mod config;
+mod events;
+mod extract;
+mod http_client;
 mod mock_data;
 mod rate_limiter;
 mod routes;
 mod state;
+mod storage;
+mod syndication;
 
+use std::collections::HashMap;
 use std::net::SocketAddr;
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 use std::time::Duration;
 
 use axum::http::{HeaderName, HeaderValue, Method};
@@ -15,9 +22,12 @@ use axum::routing::{get, post};
 use axum::Router;
 use tower_http::cors::{Any, CorsLayer};
 
-use config::RateLimitConfig;
+use analysis::alerts::{self, AlertRules};
+use config::{AdminConfig, AlertConfig, RateLimitConfig, SourceConfig, StorageConfig};
+use events::{Event, EventBus};
 use rate_limiter::RateLimiter;
 use state::AppState;
+use storage::Store;
 
 /// Middleware that adds X-Demo-Mode: true header to all responses.
 async fn demo_header_middleware(request: axum::extract::Request, next: Next) -> Response {
@@ -30,7 +40,7 @@ async fn demo_header_middleware(request: axum::extract::Request, next: Next) ->
 }
 
 #[tokio::main]
-async fn main() {
+async fn main() -> Result<(), String> {
     let port: u16 = std::env::var("PORT")
         .ok()
         .and_then(|p| p.parse().ok())
@@ -47,7 +57,126 @@ async fn main() {
         }
     });
 
-    let state = AppState { rate_limiter };
+    let storage = StorageConfig::from_env();
+    let store = Arc::new(Store::open(&storage.data_dir).map_err(|err| {
+        format!(
+            "cannot open store at {}: {}",
+            storage.data_dir.display(),
+            err
+        )
+    })?);
+
+    // Spawn background task to compact raw prices into bars and drop data
+    // that has outlived its retention.
+    let maintenance = Arc::new(RwLock::new(None));
+    let maintenance_store = Arc::clone(&store);
+    let last_maintenance = Arc::clone(&maintenance);
+    tokio::spawn(async move {
+        loop {
+            let store = Arc::clone(&maintenance_store);
+            let maintained = tokio::task::spawn_blocking(move || {
+                storage::records::maintain(&store, chrono::Utc::now())
+            })
+            .await;
+            match maintained {
+                Ok(Ok(done)) => {
+                    if let Ok(mut last) = last_maintenance.write() {
+                        *last = Some(done);
+                    }
+                }
+                Ok(Err(err)) => println!("Storage maintenance failed: {}", err),
+                Err(_) => {}
+            }
+            tokio::time::sleep(Duration::from_secs(60)).await;
+        }
+    });
+
+    // Spawn background task to record a tick of every coin's price each
+    // minute, so history does not depend on how often prices are requested.
+    let price_store = Arc::clone(&store);
+    tokio::spawn(async move {
+        loop {
+            let store = Arc::clone(&price_store);
+            let recorded = tokio::task::spawn_blocking(move || {
+                storage::records::record_prices(&store, chrono::Utc::now())
+            })
+            .await;
+            if let Ok(Err(err)) = recorded {
+                println!("Could not store crypto prices: {}", err);
+            }
+            tokio::time::sleep(Duration::from_secs(60)).await;
+        }
+    });
+
+    let sources = SourceConfig::from_env();
+    let ingested = extract::posts::ingest(&sources.social_posts);
+    for failed in ingested.iter().filter(|i| i.result.is_err()) {
+        if let Err(err) = &failed.result {
+            println!("Skipping social posts from {}: {}", failed.location, err);
+        }
+    }
+    let social_posts = extract::posts::merge(&ingested);
+
+    let mut news_articles = mock_data::news::get_feed();
+    match store.query(
+        &storage::records::ARTICLES,
+        None,
+        chrono::DateTime::UNIX_EPOCH,
+        chrono::Utc::now(),
+    ) {
+        Ok(stored) => {
+            mock_data::news::merge(&mut news_articles, stored);
+        }
+        Err(err) => println!("Serving the news feed without stored articles: {}", err),
+    }
+
+    let alert_config = AlertConfig::from_env();
+    let alert_rules = Arc::new(match &alert_config.thresholds {
+        Some(path) => AlertRules::load(path).unwrap_or_else(|err| {
+            println!(
+                "Using bundled alert thresholds; cannot read {}: {}",
+                path.display(),
+                err
+            );
+            AlertRules::bundled().clone()
+        }),
+        None => AlertRules::bundled().clone(),
+    });
+    let events = Arc::new(EventBus::new(alert_config.webhooks));
+
+    // Spawn background task to evaluate every city's weather against its
+    // alert thresholds and publish new alerts to subscribers and webhooks.
+    let weather_alerts = Arc::new(RwLock::new(HashMap::new()));
+    let alert_events = Arc::clone(&events);
+    let alert_thresholds = Arc::clone(&alert_rules);
+    let latest_alerts = Arc::clone(&weather_alerts);
+    tokio::spawn(async move {
+        loop {
+            let evaluated = alerts::evaluate_cities(&alert_thresholds);
+            for alert in evaluated.values().flatten() {
+                if let Ok(data) = serde_json::to_value(alert) {
+                    alert_events.publish(Event::new("weather.alert", &alert.id, data));
+                }
+            }
+            if let Ok(mut latest) = latest_alerts.write() {
+                *latest = evaluated;
+            }
+            tokio::time::sleep(Duration::from_secs(300)).await;
+        }
+    });
+
+    let state = AppState {
+        rate_limiter,
+        sources: Arc::new(sources),
+        admin: Arc::new(AdminConfig::from_env()),
+        social_posts: Arc::new(RwLock::new(social_posts)),
+        news_articles: Arc::new(RwLock::new(news_articles)),
+        alert_rules,
+        weather_alerts,
+        events,
+        store,
+        maintenance,
+    };
 
     let cors = CorsLayer::new()
         .allow_origin(Any)
@@ -68,22 +197,65 @@ async fn main() {
             "/api/ecommerce/products",
             get(routes::ecommerce::get_products),
         )
+        .route(
+            "/api/ecommerce/products/{product_id}",
+            get(routes::ecommerce::get_product),
+        )
+        .route(
+            "/api/ecommerce/products/{product_id}/offers",
+            get(routes::ecommerce::get_offers),
+        )
+        .route("/api/ecommerce/deals", get(routes::ecommerce::get_deals))
         .route(
             "/api/ecommerce/prices/{product_id}",
             get(routes::ecommerce::get_prices),
         )
         // Social
         .route("/api/social/trends", get(routes::social::get_trends))
+        .route("/api/social/emerging", get(routes::social::get_emerging))
+        .route("/api/social/graph", get(routes::social::get_graph))
+        .route("/api/social/platforms", get(routes::social::get_platforms))
         .route(
             "/api/social/sentiment/{topic}",
             get(routes::social::get_sentiment),
         )
+        .route(
+            "/api/social/sentiment/{topic}/history",
+            get(routes::social::get_sentiment_history),
+        )
+        .route(
+            "/api/social/sentiment/analyze",
+            post(routes::social::analyze_sentiment),
+        )
         // News
         .route("/api/news/feed", get(routes::news::get_feed))
+        .route("/api/news/categories", get(routes::news::get_categories))
+        .route("/api/news/stories", get(routes::news::get_stories))
+        .route("/api/news/entities", get(routes::news::get_entities))
+        .route("/api/news/entities/{name}", get(routes::news::get_entity))
+        .route("/api/news/feed.rss", get(routes::news::get_rss))
+        .route("/api/news/feed.atom", get(routes::news::get_atom))
         // Crypto
         .route("/api/crypto/prices", get(routes::crypto::get_prices))
+        .route(
+            "/api/crypto/prices/{symbol}/history",
+            get(routes::crypto::get_history),
+        )
         // Weather
+        .route("/api/weather/cities", get(routes::weather::get_cities))
         .route("/api/weather/{city}", get(routes::weather::get_weather))
+        .route(
+            "/api/weather/{city}/alerts",
+            get(routes::weather::get_alerts),
+        )
+        .route(
+            "/api/weather/{city}/history",
+            get(routes::weather::get_history),
+        )
+        // Admin
+        .route("/api/admin/storage", get(routes::admin::get_storage))
+        // Events
+        .route("/api/events", get(routes::events::get_events))
         .layer(middleware::from_fn(demo_header_middleware))
         .layer(cors)
         .with_state(state);
@@ -92,11 +264,13 @@ async fn main() {
     println!("DataPulse API running on http://0.0.0.0:{}", port);
     println!("Demo mode: all data is synthetic");
 
-    let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
+    let listener = tokio::net::TcpListener::bind(addr)
+        .await
+        .map_err(|err| format!("cannot listen on {}: {}", addr, err))?;
     axum::serve(
         listener,
         app.into_make_service_with_connect_info::<SocketAddr>(),
     )
     .await
-    .unwrap();
+    .map_err(|err| err.to_string())
 }
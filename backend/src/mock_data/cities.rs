@@ -0,0 +1,213 @@
+use std::sync::OnceLock;
+
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+/// Suggestions offered for a city name that matches nothing.
+const MAX_SUGGESTIONS: usize = 3;
+
+/// A city weather can be reported for.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct City {
+    /// URL-friendly identifier, e.g. `new-york`.
+    pub id: String,
+    pub name: String,
+    pub aliases: Vec<String>,
+    pub country: String,
+    /// ISO 3166-1 alpha-2 code.
+    pub country_code: String,
+    pub latitude: f64,
+    pub longitude: f64,
+    /// IANA time zone, e.g. `Europe/Istanbul`.
+    pub timezone: String,
+}
+
+impl City {
+    /// The city's time zone; the registry test checks every name parses.
+    pub fn tz(&self) -> Tz {
+        self.timezone.parse().unwrap_or(Tz::UTC)
+    }
+
+    /// The id, name and aliases, folded for comparison.
+    fn keys(&self) -> impl Iterator<Item = String> + '_ {
+        [&self.id, &self.name]
+            .into_iter()
+            .chain(&self.aliases)
+            .map(|name| fold(name))
+    }
+}
+
+/// Return the bundled city registry from `cities.json`.
+pub fn cities() -> &'static [City] {
+    static CITIES: OnceLock<Vec<City>> = OnceLock::new();
+    CITIES.get_or_init(|| {
+        serde_json::from_str(include_str!("cities.json")).expect("cities.json must be valid")
+    })
+}
+
+/// Find the city whose id, name or alias matches `query`, ignoring case,
+/// diacritics, spaces and punctuation, so `İstanbul`, `istanbul` and
+/// `new-york` all resolve.
+pub fn lookup(query: &str) -> Option<&'static City> {
+    let wanted = fold(query);
+    if wanted.is_empty() {
+        return None;
+    }
+    cities()
+        .iter()
+        .find(|city| city.keys().any(|key| key == wanted))
+}
+
+/// Cities whose id, name or alias contains `query` (all cities without
+/// one), optionally limited to a country name or code. Names starting with
+/// the query come first, then alphabetical order.
+pub fn search(query: Option<&str>, country: Option<&str>) -> Vec<&'static City> {
+    let wanted = query.map(fold).unwrap_or_default();
+    let country = country.map(fold);
+    let mut found: Vec<(bool, &City)> = cities()
+        .iter()
+        .filter(|city| {
+            country
+                .as_ref()
+                .is_none_or(|c| fold(&city.country) == *c || fold(&city.country_code) == *c)
+        })
+        .filter_map(|city| {
+            let mut keys = city.keys();
+            if keys.any(|key| key.contains(&wanted)) {
+                Some((!city.keys().any(|key| key.starts_with(&wanted)), city))
+            } else {
+                None
+            }
+        })
+        .collect();
+    found.sort_by(|a, b| (a.0, &a.1.name).cmp(&(b.0, &b.1.name)));
+    found.into_iter().map(|(_, city)| city).collect()
+}
+
+/// The closest city names to an unknown `query`, for "did you mean" hints:
+/// names it begins, or within an edit distance of about a third of its length.
+pub fn suggestions(query: &str) -> Vec<&'static City> {
+    let wanted = fold(query);
+    if wanted.is_empty() {
+        return Vec::new();
+    }
+    let max_distance = (wanted.chars().count() / 3).max(2);
+    let mut close: Vec<(usize, &City)> = cities()
+        .iter()
+        .filter_map(|city| {
+            city.keys()
+                .map(|key| {
+                    if wanted.chars().count() >= 3 && key.starts_with(&wanted) {
+                        0
+                    } else {
+                        edit_distance(&wanted, &key)
+                    }
+                })
+                .min()
+                .filter(|&distance| distance <= max_distance)
+                .map(|distance| (distance, city))
+        })
+        .collect();
+    close.sort_by(|a, b| (a.0, &a.1.name).cmp(&(b.0, &b.1.name)));
+    close
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, city)| city)
+        .collect()
+}
+
+/// Lowercase, strip diacritics and drop everything but letters and digits.
+/// Letters that do not decompose (dotless ı, ø, ß, ...) are spelled out.
+fn fold(text: &str) -> String {
+    text.nfd()
+        .filter(|c| !is_combining_mark(*c))
+        .flat_map(char::to_lowercase)
+        .filter(|c| c.is_alphanumeric())
+        .map(|c| match c {
+            'ı' => "i".to_string(),
+            'ø' => "o".to_string(),
+            'ł' => "l".to_string(),
+            'đ' => "d".to_string(),
+            'æ' => "ae".to_string(),
+            'ß' => "ss".to_string(),
+            other => other.to_string(),
+        })
+        .collect()
+}
+
+/// Levenshtein distance between two strings, counted in characters.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, &cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registry_is_consistent() {
+        let cities = cities();
+        for (i, city) in cities.iter().enumerate() {
+            assert!(city.latitude.abs() <= 90.0 && city.longitude.abs() <= 180.0);
+            assert_eq!(city.country_code.len(), 2, "{}", city.id);
+            assert!(city.timezone.parse::<Tz>().is_ok(), "{}", city.timezone);
+            for other in &cities[i + 1..] {
+                assert!(
+                    city.keys().all(|key| other.keys().all(|k| k != key)),
+                    "{} and {} share a name",
+                    city.id,
+                    other.id
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn lookup_ignores_case_and_diacritics() {
+        for query in ["istanbul", "İstanbul", "ISTANBUL", " Istanbul "] {
+            assert_eq!(lookup(query).map(|c| c.id.as_str()), Some("istanbul"));
+        }
+        assert_eq!(lookup("sao paulo").unwrap().id, "sao-paulo");
+        assert_eq!(lookup("NEWYORK").unwrap().id, "new-york");
+        assert_eq!(lookup("new-york").unwrap().id, "new-york");
+        assert_eq!(lookup("München").unwrap().id, "munich");
+        assert_eq!(lookup("izmır").unwrap().id, "izmir");
+        assert!(lookup("atlantis").is_none());
+        assert!(lookup("").is_none());
+    }
+
+    #[test]
+    fn suggests_close_names() {
+        let ids = |query| -> Vec<&str> {
+            suggestions(query)
+                .into_iter()
+                .map(|c| c.id.as_str())
+                .collect()
+        };
+        assert_eq!(ids("Istambul"), vec!["istanbul"]);
+        assert_eq!(ids("Lond"), vec!["london"]);
+        assert!(ids("atlantis").is_empty());
+    }
+
+    #[test]
+    fn searches_by_substring_and_country() {
+        let names: Vec<&str> = search(Some("an"), Some("tr"))
+            .into_iter()
+            .map(|c| c.id.as_str())
+            .collect();
+        assert_eq!(names, vec!["ankara", "antalya", "istanbul"]);
+        assert_eq!(search(None, None).len(), cities().len());
+    }
+}
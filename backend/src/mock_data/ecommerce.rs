@@ -1,6 +1,10 @@
-use chrono::Utc;
-use rand::Rng;
-use serde::Serialize;
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Timelike, Utc};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::analysis::fnv1a;
 
 #[derive(Debug, Clone, Serialize)]
 pub struct Product {
@@ -15,6 +19,21 @@ pub struct Product {
     pub review_count: u32,
     pub in_stock: bool,
     pub url: String,
+    pub offers: Vec<Offer>,
+    pub best_price: f64,
+    pub best_price_source: String,
+}
+
+/// A single retailer's listing of a product.
+#[derive(Debug, Clone, Serialize)]
+pub struct Offer {
+    pub source: String,
+    pub price: f64,
+    pub currency: String,
+    pub in_stock: bool,
+    /// Units available, when the retailer discloses it.
+    pub stock_count: Option<u32>,
+    pub url: String,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -23,100 +42,761 @@ pub struct PricePoint {
     pub price: f64,
 }
 
-/// Return 20 mock products with slight random variation on each call.
+#[derive(Debug, Clone, Serialize)]
+pub struct PriceSummary {
+    pub min: f64,
+    pub max: f64,
+    pub avg: f64,
+    pub largest_drop: Option<PriceMove>,
+    pub largest_rise: Option<PriceMove>,
+}
+
+/// A price movement between two points of a series.
+#[derive(Debug, Clone, Serialize)]
+pub struct PriceMove {
+    pub from: String,
+    pub to: String,
+    pub from_price: f64,
+    pub to_price: f64,
+    pub change: f64,
+    pub change_percent: f64,
+}
+
+impl PriceMove {
+    fn between(start: &PricePoint, end: &PricePoint) -> Self {
+        let change = end.price - start.price;
+        Self {
+            from: start.date.clone(),
+            to: end.date.clone(),
+            from_price: start.price,
+            to_price: end.price,
+            change: round_price(change),
+            change_percent: round_price(change / start.price * 100.0),
+        }
+    }
+}
+
+/// Bucket size for price history queries.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Granularity {
+    Hour,
+    #[default]
+    Day,
+    Week,
+}
+
+/// A product whose current price sits below its recent baseline.
+#[derive(Debug, Clone, Serialize)]
+pub struct Deal {
+    pub product_id: String,
+    pub name: String,
+    pub category: String,
+    pub source: String,
+    pub current_price: f64,
+    pub baseline_price: f64,
+    pub drop_percent: f64,
+    /// Highest price seen in the markup window just before today.
+    pub recent_peak: f64,
+    /// The discount a shopper would see if the retailer quoted `recent_peak`.
+    pub advertised_drop_percent: f64,
+    pub suspected_fake_discount: bool,
+}
+
+/// Thresholds for the deal feed; every field may be overridden by query string.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DealCriteria {
+    /// Days of history whose median forms the baseline price.
+    pub baseline_days: usize,
+    /// Minimum drop below the baseline, in percent, to count as a deal.
+    pub min_drop_percent: f64,
+    /// Days before today inspected for a price hike preceding the drop.
+    pub markup_window_days: usize,
+    /// Rise above the baseline, in percent, that marks a hike as suspicious.
+    pub markup_percent: f64,
+}
+
+impl Default for DealCriteria {
+    fn default() -> Self {
+        Self {
+            baseline_days: 30,
+            min_drop_percent: 3.0,
+            markup_window_days: 7,
+            markup_percent: 4.0,
+        }
+    }
+}
+
+impl DealCriteria {
+    /// Whether every threshold is in range. Percentages must lie within
+    /// `0..=100`, which also rules out NaN, and the markup window must fit
+    /// inside the baseline.
+    pub fn is_valid(&self) -> bool {
+        (7..=180).contains(&self.baseline_days)
+            && (1..=self.baseline_days).contains(&self.markup_window_days)
+            && (0.0..=100.0).contains(&self.min_drop_percent)
+            && (0.0..=100.0).contains(&self.markup_percent)
+    }
+}
+
+/// The product catalogue shared by every e-commerce endpoint:
+/// (id, name, category, base price, source, rating, review count).
+const CATALOG: &[(&str, &str, &str, f64, &str, f64, u32)] = &[
+    ("elec-001", "Samsung Galaxy S24 Ultra", "Electronics", 42999.0, "Trendyol", 4.8, 3421),
+    ("elec-002", "Apple MacBook Air M3", "Electronics", 54999.0, "Hepsiburada", 4.9, 1876),
+    ("elec-003", "Sony WH-1000XM5 Headphones", "Electronics", 11499.0, "Trendyol", 4.7, 2103),
+    ("elec-004", "iPad Pro 12.9\" M2", "Electronics", 38999.0, "Hepsiburada", 4.8, 945),
+    ("elec-005", "LG C3 65\" OLED TV", "Electronics", 64999.0, "Trendyol", 4.9, 567),
+    ("elec-006", "Dyson V15 Detect Vacuum", "Electronics", 24999.0, "Hepsiburada", 4.6, 832),
+    ("elec-007", "Logitech MX Master 3S", "Electronics", 3299.0, "Trendyol", 4.7, 4521),
+    ("elec-008", "PlayStation 5 Slim", "Electronics", 18499.0, "Hepsiburada", 4.8, 6712),
+    ("elec-009", "Anker 737 Power Bank", "Electronics", 2799.0, "Trendyol", 4.5, 1543),
+    ("elec-010", "Bose QuietComfort Ultra", "Electronics", 13999.0, "Hepsiburada", 4.6, 987),
+    ("cloth-001", "Nike Air Max 270", "Clothing", 4299.0, "Trendyol", 4.5, 8934),
+    ("cloth-002", "Adidas Ultraboost 23", "Clothing", 5199.0, "Hepsiburada", 4.6, 3210),
+    ("cloth-003", "Levi's 501 Original Jeans", "Clothing", 2499.0, "Trendyol", 4.4, 5678),
+    ("cloth-004", "The North Face Thermoball Jacket", "Clothing", 7999.0, "Hepsiburada", 4.7, 1234),
+    ("cloth-005", "Zara Wool Blend Overcoat", "Clothing", 3999.0, "Trendyol", 4.3, 2456),
+    ("cloth-006", "H&M Regular Fit Oxford Shirt", "Clothing", 799.0, "Hepsiburada", 4.2, 7890),
+    ("cloth-007", "Puma RS-X Sneakers", "Clothing", 3599.0, "Trendyol", 4.4, 1567),
+    ("cloth-008", "Columbia Hiking Boots", "Clothing", 4799.0, "Hepsiburada", 4.6, 2890),
+    ("cloth-009", "Mango Knit Sweater", "Clothing", 1299.0, "Trendyol", 4.1, 3456),
+    ("cloth-010", "Tommy Hilfiger Polo Shirt", "Clothing", 1899.0, "Hepsiburada", 4.5, 4123),
+];
+
+/// Days between the seeded levels a product's price walk is pinned to.
+const CHECKPOINT_DAYS: i64 = 28;
+
+/// Largest distance of a checkpoint level from the base price, as a share.
+const CHECKPOINT_SPREAD: f64 = 0.08;
+
+/// Retailers that may list catalogue products: (name, domain).
+const RETAILERS: &[(&str, &str)] = &[
+    ("Trendyol", "trendyol.com"),
+    ("Hepsiburada", "hepsiburada.com"),
+    ("n11", "n11.com"),
+    ("Amazon TR", "amazon.com.tr"),
+];
+
+/// Return all 20 catalogue products with their 30-day price history.
 pub fn get_products() -> Vec<Product> {
-    let mut rng = rand::thread_rng();
-
-    let base_products = vec![
-        ("elec-001", "Samsung Galaxy S24 Ultra", "Electronics", 42999.0, "Trendyol", 4.8, 3421),
-        ("elec-002", "Apple MacBook Air M3", "Electronics", 54999.0, "Hepsiburada", 4.9, 1876),
-        ("elec-003", "Sony WH-1000XM5 Headphones", "Electronics", 11499.0, "Trendyol", 4.7, 2103),
-        ("elec-004", "iPad Pro 12.9\" M2", "Electronics", 38999.0, "Hepsiburada", 4.8, 945),
-        ("elec-005", "LG C3 65\" OLED TV", "Electronics", 64999.0, "Trendyol", 4.9, 567),
-        ("elec-006", "Dyson V15 Detect Vacuum", "Electronics", 24999.0, "Hepsiburada", 4.6, 832),
-        ("elec-007", "Logitech MX Master 3S", "Electronics", 3299.0, "Trendyol", 4.7, 4521),
-        ("elec-008", "PlayStation 5 Slim", "Electronics", 18499.0, "Hepsiburada", 4.8, 6712),
-        ("elec-009", "Anker 737 Power Bank", "Electronics", 2799.0, "Trendyol", 4.5, 1543),
-        ("elec-010", "Bose QuietComfort Ultra", "Electronics", 13999.0, "Hepsiburada", 4.6, 987),
-        ("cloth-001", "Nike Air Max 270", "Clothing", 4299.0, "Trendyol", 4.5, 8934),
-        ("cloth-002", "Adidas Ultraboost 23", "Clothing", 5199.0, "Hepsiburada", 4.6, 3210),
-        ("cloth-003", "Levi's 501 Original Jeans", "Clothing", 2499.0, "Trendyol", 4.4, 5678),
-        ("cloth-004", "The North Face Thermoball Jacket", "Clothing", 7999.0, "Hepsiburada", 4.7, 1234),
-        ("cloth-005", "Zara Wool Blend Overcoat", "Clothing", 3999.0, "Trendyol", 4.3, 2456),
-        ("cloth-006", "H&M Regular Fit Oxford Shirt", "Clothing", 799.0, "Hepsiburada", 4.2, 7890),
-        ("cloth-007", "Puma RS-X Sneakers", "Clothing", 3599.0, "Trendyol", 4.4, 1567),
-        ("cloth-008", "Columbia Hiking Boots", "Clothing", 4799.0, "Hepsiburada", 4.6, 2890),
-        ("cloth-009", "Mango Knit Sweater", "Clothing", 1299.0, "Trendyol", 4.1, 3456),
-        ("cloth-010", "Tommy Hilfiger Polo Shirt", "Clothing", 1899.0, "Hepsiburada", 4.5, 4123),
-    ];
-
-    base_products
-        .into_iter()
-        .map(|(id, name, category, base_price, source, rating, reviews)| {
-            let variation: f64 = rng.gen_range(-0.02..0.02);
-            let price = (base_price * (1.0 + variation) * 100.0).round() / 100.0;
+    CATALOG.iter().map(build_product).collect()
+}
 
-            let price_history = generate_price_history(base_price, 30, &mut rng);
+/// Return a single product, or `None` if the ID is not in the catalogue.
+pub fn get_product(product_id: &str) -> Option<Product> {
+    CATALOG
+        .iter()
+        .find(|entry| entry.0 == product_id)
+        .map(build_product)
+}
 
-            Product {
-                id: id.to_string(),
-                name: name.to_string(),
-                category: category.to_string(),
-                price,
-                currency: "TRY".to_string(),
-                price_history,
-                source: source.to_string(),
-                rating: ((rating as f64 + rng.gen_range(-0.1_f64..0.1)) * 10.0).round() / 10.0,
-                review_count: (reviews as f64 * (1.0 + rng.gen_range(-0.05..0.05))) as u32,
-                in_stock: rng.gen_bool(0.85),
-                url: format!("https://{}.com/p/{}", source.to_lowercase(), id),
-            }
+/// Return every retailer offer for a product, cheapest first, or `None` if unknown.
+pub fn get_offers(product_id: &str) -> Option<Vec<Offer>> {
+    let mut offers = get_product(product_id)?.offers;
+    offers.sort_by(|a, b| a.price.total_cmp(&b.price));
+    Some(offers)
+}
+
+/// Rank catalogue products by how far today's price sits below the median of
+/// their recent history, flagging drops that follow a sudden price hike.
+pub fn get_deals(criteria: &DealCriteria) -> Vec<Deal> {
+    let mut deals: Vec<Deal> = CATALOG
+        .iter()
+        .filter_map(|entry| {
+            let history = generate_price_history(entry.0, entry.3, criteria.baseline_days + 1);
+            find_deal(entry, &history, criteria)
         })
-        .collect()
+        .collect();
+
+    deals.sort_by(|a, b| b.drop_percent.total_cmp(&a.drop_percent));
+    deals
 }
 
-/// Return 30-day price history for a given product.
-pub fn get_price_trends(product_id: &str) -> Vec<PricePoint> {
-    let mut rng = rand::thread_rng();
-
-    let base_price = match product_id {
-        "elec-001" => 42999.0,
-        "elec-002" => 54999.0,
-        "elec-003" => 11499.0,
-        "elec-004" => 38999.0,
-        "elec-005" => 64999.0,
-        "elec-006" => 24999.0,
-        "elec-007" => 3299.0,
-        "elec-008" => 18499.0,
-        "elec-009" => 2799.0,
-        "elec-010" => 13999.0,
-        "cloth-001" => 4299.0,
-        "cloth-002" => 5199.0,
-        "cloth-003" => 2499.0,
-        "cloth-004" => 7999.0,
-        "cloth-005" => 3999.0,
-        "cloth-006" => 799.0,
-        "cloth-007" => 3599.0,
-        "cloth-008" => 4799.0,
-        "cloth-009" => 1299.0,
-        "cloth-010" => 1899.0,
-        _ => 1000.0,
-    };
+/// Judge the last point of `history` against the median of the points before
+/// it, or `None` if it is not far enough below that baseline.
+fn find_deal(
+    entry: &(&str, &str, &str, f64, &str, f64, u32),
+    history: &[PricePoint],
+    criteria: &DealCriteria,
+) -> Option<Deal> {
+    let &(id, name, category, _, source, _, _) = entry;
+    let (today, previous) = history.split_last()?;
+
+    let mut window: Vec<f64> = previous.iter().map(|p| p.price).collect();
+    window.sort_by(f64::total_cmp);
+    let baseline = median(&window)?;
+    let drop_percent = (baseline - today.price) / baseline * 100.0;
+    if drop_percent < criteria.min_drop_percent {
+        return None;
+    }
 
-    generate_price_history(base_price, 30, &mut rng)
+    let markup_start = previous.len().saturating_sub(criteria.markup_window_days);
+    let recent_peak = previous[markup_start..]
+        .iter()
+        .map(|p| p.price)
+        .fold(today.price, f64::max);
+    let advertised_drop_percent = (recent_peak - today.price) / recent_peak * 100.0;
+
+    Some(Deal {
+        product_id: id.to_string(),
+        name: name.to_string(),
+        category: category.to_string(),
+        source: source.to_string(),
+        current_price: today.price,
+        baseline_price: round_price(baseline),
+        drop_percent: round_price(drop_percent),
+        recent_peak,
+        advertised_drop_percent: round_price(advertised_drop_percent),
+        suspected_fake_discount: recent_peak >= baseline * (1.0 + criteria.markup_percent / 100.0),
+    })
 }
 
-fn generate_price_history(base_price: f64, days: usize, rng: &mut impl Rng) -> Vec<PricePoint> {
-    let now = Utc::now();
-    (0..days)
-        .map(|i| {
-            let date = now - chrono::Duration::days((days - 1 - i) as i64);
-            let variation: f64 = rng.gen_range(-0.05..0.05);
-            let price = (base_price * (1.0 + variation) * 100.0).round() / 100.0;
-            PricePoint {
+/// Return the price history of a product between `from` and `to` (inclusive),
+/// bucketed at the requested granularity, or `None` if the product is unknown.
+pub fn get_price_history(
+    product_id: &str,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    granularity: Granularity,
+) -> Option<Vec<PricePoint>> {
+    let &(id, _, _, base_price, _, _, _) = CATALOG.iter().find(|entry| entry.0 == product_id)?;
+    let from = from.max(history_epoch());
+    if from > to {
+        return Some(Vec::new());
+    }
+
+    let closes = daily_closes(id, base_price, from.date_naive(), to.date_naive());
+    let points = match granularity {
+        Granularity::Day => closes
+            .iter()
+            .skip(1)
+            .map(|&(date, price)| PricePoint {
                 date: date.format("%Y-%m-%d").to_string(),
-                price,
+                price: round_price(price),
+            })
+            .collect(),
+        Granularity::Hour => closes
+            .windows(2)
+            .flat_map(|pair| {
+                let (_, open) = pair[0];
+                let (date, close) = pair[1];
+                (0..24).filter_map(move |hour| {
+                    let at = date.and_hms_opt(hour, 0, 0)?.and_utc();
+                    (at >= from && at <= to).then(|| PricePoint {
+                        date: at.format("%Y-%m-%dT%H:00:00Z").to_string(),
+                        price: round_price(hourly_price(id, base_price, at, open, close)),
+                    })
+                })
+            })
+            .collect(),
+        Granularity::Week => {
+            // Each week is reported by its Monday with the last close seen in it.
+            let mut weeks: Vec<PricePoint> = Vec::new();
+            for &(date, price) in closes.iter().skip(1) {
+                let monday = date - Duration::days(date.weekday().num_days_from_monday() as i64);
+                let label = monday.format("%Y-%m-%d").to_string();
+                match weeks.last_mut() {
+                    Some(last) if last.date == label => last.price = round_price(price),
+                    _ => weeks.push(PricePoint {
+                        date: label,
+                        price: round_price(price),
+                    }),
+                }
             }
+            weeks
+        }
+    };
+
+    Some(points)
+}
+
+/// Compute min/max/avg and the largest drop and rise over a price series.
+pub fn summarize(points: &[PricePoint]) -> Option<PriceSummary> {
+    let first = points.first()?;
+    let min = points.iter().map(|p| p.price).fold(f64::INFINITY, f64::min);
+    let max = points
+        .iter()
+        .map(|p| p.price)
+        .fold(f64::NEG_INFINITY, f64::max);
+    let avg = points.iter().map(|p| p.price).sum::<f64>() / points.len() as f64;
+
+    // Track the running peak and trough to find the biggest decline from a
+    // prior high and the biggest recovery from a prior low.
+    let (mut peak, mut trough) = (first, first);
+    let mut largest_drop: Option<PriceMove> = None;
+    let mut largest_rise: Option<PriceMove> = None;
+    for point in points {
+        if point.price > peak.price {
+            peak = point;
+        }
+        if point.price < trough.price {
+            trough = point;
+        }
+        if point.price < peak.price
+            && largest_drop
+                .as_ref()
+                .is_none_or(|m| point.price - peak.price < m.change)
+        {
+            largest_drop = Some(PriceMove::between(peak, point));
+        }
+        if point.price > trough.price
+            && largest_rise
+                .as_ref()
+                .is_none_or(|m| point.price - trough.price > m.change)
+        {
+            largest_rise = Some(PriceMove::between(trough, point));
+        }
+    }
+
+    Some(PriceSummary {
+        min,
+        max,
+        avg: round_price(avg),
+        largest_drop,
+        largest_rise,
+    })
+}
+
+fn build_product(entry: &(&str, &str, &str, f64, &str, f64, u32)) -> Product {
+    let &(id, name, category, base_price, source, rating, reviews) = entry;
+    let today = Utc::now().format("%Y-%m-%d").to_string();
+
+    // Seed per product and day so every endpoint agrees on today's values.
+    let mut rng = StdRng::seed_from_u64(seed_for(id, &format!("{today}:listing")));
+    let price_history = generate_price_history(id, base_price, 30);
+    let price = price_history.last().map(|p| p.price).unwrap_or(base_price);
+    let offers = build_offers(id, source, price, &today);
+
+    let (best_price, best_price_source) = best_offer(&offers)
+        .map(|o| (o.price, o.source.clone()))
+        .unwrap_or((price, source.to_string()));
+
+    Product {
+        id: id.to_string(),
+        name: name.to_string(),
+        category: category.to_string(),
+        price,
+        currency: "TRY".to_string(),
+        price_history,
+        source: source.to_string(),
+        rating: ((rating + rng.gen_range(-0.1_f64..0.1)) * 10.0).round() / 10.0,
+        review_count: (reviews as f64 * (1.0 + rng.gen_range(-0.05..0.05))) as u32,
+        in_stock: offers.iter().any(|o| o.in_stock),
+        url: format!("https://{}.com/p/{}", source.to_lowercase(), id),
+        offers,
+        best_price,
+        best_price_source,
+    }
+}
+
+/// The cheapest offer that can actually be bought, or the cheapest overall
+/// when every retailer is out of stock.
+pub fn best_offer(offers: &[Offer]) -> Option<&Offer> {
+    let by_price = |a: &&Offer, b: &&Offer| a.price.total_cmp(&b.price);
+    offers
+        .iter()
+        .filter(|o| o.in_stock)
+        .min_by(by_price)
+        .or_else(|| offers.iter().min_by(by_price))
+}
+
+/// Build today's offers for a product. The home retailer always lists it at the
+/// tracked price; other retailers list it some of the time at their own markup.
+fn build_offers(product_id: &str, home: &str, price: f64, today: &str) -> Vec<Offer> {
+    RETAILERS
+        .iter()
+        .filter_map(|&(retailer, domain)| {
+            let mut listing = StdRng::seed_from_u64(seed_for(product_id, retailer));
+            let is_home = retailer == home;
+            if !is_home && !listing.gen_bool(0.6) {
+                return None;
+            }
+
+            let mut rng =
+                StdRng::seed_from_u64(seed_for(product_id, &format!("{today}:{retailer}")));
+            let offer_price = if is_home {
+                price
+            } else {
+                round_price(price * (1.0 + rng.gen_range(-0.04..0.06)))
+            };
+            let in_stock = rng.gen_bool(0.85);
+
+            Some(Offer {
+                source: retailer.to_string(),
+                price: offer_price,
+                currency: "TRY".to_string(),
+                in_stock,
+                stock_count: Some(if in_stock { rng.gen_range(1..250) } else { 0 }),
+                url: format!("https://{}/p/{}", domain, product_id),
+            })
+        })
+        .collect()
+}
+
+fn generate_price_history(product_id: &str, base_price: f64, days: usize) -> Vec<PricePoint> {
+    let today = Utc::now().date_naive();
+    let start = today - Duration::days(days as i64 - 1);
+    daily_closes(product_id, base_price, start, today)
+        .into_iter()
+        .skip(1)
+        .map(|(date, price)| PricePoint {
+            date: date.format("%Y-%m-%d").to_string(),
+            price: round_price(price),
+        })
+        .collect()
+}
+
+/// First day of the simulated price series; history requests are clamped to it.
+fn history_epoch() -> DateTime<Utc> {
+    NaiveDate::from_ymd_opt(2025, 1, 1)
+        .and_then(|d| d.and_hms_opt(0, 0, 0))
+        .map(|dt| dt.and_utc())
+        .unwrap_or_default()
+}
+
+/// Return the close of the day before `start` followed by every close in
+/// `start..=end`.
+///
+/// Closes are a random walk: each day adds a shock seeded by product and
+/// date to the day before. Every `CHECKPOINT_DAYS` days from the epoch the
+/// walk passes through a level seeded the same way, and each stretch between
+/// two checkpoints is a bridge: its running total of shocks is tilted so it
+/// lands on the next level. A close therefore depends only on its date and
+/// takes at most one stretch to compute, without replaying from the epoch.
+fn daily_closes(
+    product_id: &str,
+    base_price: f64,
+    start: NaiveDate,
+    end: NaiveDate,
+) -> Vec<(NaiveDate, f64)> {
+    let epoch = history_epoch().date_naive();
+    let mut stretches: HashMap<i64, Vec<f64>> = HashMap::new();
+    (start - Duration::days(1))
+        .iter_days()
+        .take_while(|date| *date <= end)
+        .map(|date| {
+            let offset = (date - epoch).num_days();
+            let index = offset.div_euclid(CHECKPOINT_DAYS);
+            let stretch = stretches.entry(index).or_insert_with(|| {
+                let from = epoch + Duration::days(index * CHECKPOINT_DAYS);
+                walk_stretch(product_id, base_price, from)
+            });
+            (date, stretch[offset.rem_euclid(CHECKPOINT_DAYS) as usize])
         })
         .collect()
 }
+
+/// The closes of the `CHECKPOINT_DAYS` days starting at the checkpoint
+/// `from`: its level, then one seeded shock a day, bridged to the level of
+/// the next checkpoint.
+fn walk_stretch(product_id: &str, base_price: f64, from: NaiveDate) -> Vec<f64> {
+    let seeded = |date: NaiveDate, key: &str| {
+        StdRng::seed_from_u64(seed_for(
+            product_id,
+            &format!("{}{key}", date.format("%Y-%m-%d")),
+        ))
+    };
+    let level = |date| {
+        let shift = seeded(date, ":checkpoint").gen_range(-CHECKPOINT_SPREAD..CHECKPOINT_SPREAD);
+        base_price * (1.0 + shift)
+    };
+    let (open, close) = (level(from), level(from + Duration::days(CHECKPOINT_DAYS)));
+
+    // Running total of the shocks after the checkpoint, as a share of base.
+    let walk: Vec<f64> = std::iter::once(0.0)
+        .chain(
+            (1..=CHECKPOINT_DAYS)
+                .map(|day| seeded(from + Duration::days(day), "").gen_range(-0.02..0.02))
+                .scan(0.0, |total, shock| {
+                    *total += shock;
+                    Some(*total)
+                }),
+        )
+        .collect();
+    let total = walk[CHECKPOINT_DAYS as usize];
+    (0..CHECKPOINT_DAYS as usize)
+        .map(|day| {
+            let progress = day as f64 / CHECKPOINT_DAYS as f64;
+            open + (close - open) * progress + base_price * (walk[day] - total * progress)
+        })
+        .collect()
+}
+
+/// Interpolate an hourly price between two daily closes, with seeded intraday noise
+/// that vanishes at the end of the day so the last hour matches the daily close.
+fn hourly_price(
+    product_id: &str,
+    base_price: f64,
+    at: DateTime<Utc>,
+    open: f64,
+    close: f64,
+) -> f64 {
+    let progress = (at.hour() + 1) as f64 / 24.0;
+    let mut rng =
+        StdRng::seed_from_u64(seed_for(product_id, &at.format("%Y-%m-%dT%H").to_string()));
+    let noise: f64 = rng.gen_range(-0.004..0.004) * (1.0 - progress);
+    open + (close - open) * progress + noise * base_price
+}
+
+fn median(sorted: &[f64]) -> Option<f64> {
+    let mid = sorted.len() / 2;
+    match sorted.len() {
+        0 => None,
+        n if n % 2 == 0 => Some((sorted[mid - 1] + sorted[mid]) / 2.0),
+        _ => Some(sorted[mid]),
+    }
+}
+
+fn round_price(price: f64) -> f64 {
+    (price * 100.0).round() / 100.0
+}
+
+/// Hash of the product ID and a date key, used to seed per-day values.
+fn seed_for(product_id: &str, key: &str) -> u64 {
+    fnv1a(format!("{product_id}:{key}").as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(raw: &str) -> DateTime<Utc> {
+        raw.parse().unwrap()
+    }
+
+    #[test]
+    fn closes_depend_only_on_the_date() {
+        let day = |d| NaiveDate::from_ymd_opt(2026, 3, d).unwrap();
+        let month = daily_closes("elec-001", 42999.0, day(1), day(31));
+        let week = daily_closes("elec-001", 42999.0, day(10), day(16));
+        assert_eq!(month.len(), 32);
+        assert_eq!(week.len(), 8);
+        assert_eq!(week[..], month[9..17]);
+        assert!(month
+            .iter()
+            .all(|&(_, price)| (price / 42999.0 - 1.0).abs() <= 0.2));
+    }
+
+    #[test]
+    fn closes_walk_by_daily_steps_through_checkpoints() {
+        let epoch = history_epoch().date_naive();
+        let end = epoch + Duration::days(3 * CHECKPOINT_DAYS);
+        let closes = daily_closes("cloth-003", 2499.0, epoch + Duration::days(1), end);
+        // A day moves the price by its shock plus a share of the bridge, never
+        // by a jump, including across checkpoints.
+        assert!(closes
+            .windows(2)
+            .all(|pair| (pair[1].1 - pair[0].1).abs() <= 2499.0 * 0.05));
+        for checkpoint in 0..=3 {
+            let (date, price) = closes[(checkpoint * CHECKPOINT_DAYS) as usize];
+            let level = walk_stretch("cloth-003", 2499.0, date)[0];
+            assert_eq!(price, level);
+            assert!((price / 2499.0 - 1.0).abs() <= CHECKPOINT_SPREAD);
+        }
+    }
+
+    #[test]
+    fn history_clamped_to_the_epoch_keeps_its_first_day() {
+        let points = get_price_history(
+            "elec-001",
+            at("2024-12-20T00:00:00Z"),
+            at("2025-01-03T00:00:00Z"),
+            Granularity::Day,
+        )
+        .unwrap();
+        let dates: Vec<_> = points.iter().map(|p| p.date.as_str()).collect();
+        assert_eq!(dates, ["2025-01-01", "2025-01-02", "2025-01-03"]);
+    }
+
+    #[test]
+    fn rejects_out_of_range_deal_criteria() {
+        assert!(DealCriteria::default().is_valid());
+        let with = |change: fn(&mut DealCriteria)| {
+            let mut criteria = DealCriteria::default();
+            change(&mut criteria);
+            criteria.is_valid()
+        };
+        assert!(!with(|c| c.baseline_days = 6));
+        assert!(!with(|c| c.markup_window_days = 0));
+        assert!(!with(|c| c.markup_window_days = c.baseline_days + 1));
+        assert!(!with(|c| c.min_drop_percent = f64::NAN));
+        assert!(!with(|c| c.min_drop_percent = -1.0));
+        assert!(!with(|c| c.markup_percent = f64::NAN));
+        assert!(!with(|c| c.markup_percent = 150.0));
+        assert!(with(|c| c.min_drop_percent = 0.0));
+    }
+
+    fn series(prices: &[f64]) -> Vec<PricePoint> {
+        prices
+            .iter()
+            .enumerate()
+            .map(|(day, &price)| PricePoint {
+                date: format!("2026-03-{:02}", day + 1),
+                price,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn summarizes_largest_drop_and_rise_from_prior_extremes() {
+        let summary = summarize(&series(&[100.0, 120.0, 90.0, 95.0, 80.0, 130.0])).unwrap();
+        assert_eq!(
+            (summary.min, summary.max, summary.avg),
+            (80.0, 130.0, 102.5)
+        );
+
+        let drop = summary.largest_drop.unwrap();
+        assert_eq!(
+            (drop.from.as_str(), drop.to.as_str()),
+            ("2026-03-02", "2026-03-05")
+        );
+        assert_eq!((drop.change, drop.change_percent), (-40.0, -33.33));
+        let rise = summary.largest_rise.unwrap();
+        assert_eq!(
+            (rise.from.as_str(), rise.to.as_str()),
+            ("2026-03-05", "2026-03-06")
+        );
+        assert_eq!((rise.change, rise.change_percent), (50.0, 62.5));
+
+        let flat = summarize(&series(&[10.0, 10.0])).unwrap();
+        assert!(flat.largest_drop.is_none() && flat.largest_rise.is_none());
+        assert!(summarize(&[]).is_none());
+    }
+
+    #[test]
+    fn hourly_history_ends_each_day_on_its_close() {
+        let (from, to) = (at("2026-03-09T00:00:00Z"), at("2026-03-10T23:59:59Z"));
+        let hourly = get_price_history("cloth-003", from, to, Granularity::Hour).unwrap();
+        let daily = get_price_history("cloth-003", from, to, Granularity::Day).unwrap();
+        assert_eq!(hourly.len(), 48);
+        assert_eq!(hourly[0].date, "2026-03-09T00:00:00Z");
+        assert_eq!(hourly[23].price, daily[0].price);
+        assert_eq!(hourly[47].price, daily[1].price);
+
+        let partial = get_price_history(
+            "cloth-003",
+            at("2026-03-09T06:00:00Z"),
+            at("2026-03-09T09:30:00Z"),
+            Granularity::Hour,
+        )
+        .unwrap();
+        let hours: Vec<_> = partial.iter().map(|p| &p.date[11..13]).collect();
+        assert_eq!(hours, ["06", "07", "08", "09"]);
+        assert!(get_price_history("nope", from, to, Granularity::Day).is_none());
+    }
+
+    #[test]
+    fn best_offer_prefers_cheapest_in_stock() {
+        let offer = |source: &str, price, in_stock| Offer {
+            source: source.to_string(),
+            price,
+            currency: "TRY".to_string(),
+            in_stock,
+            stock_count: Some(if in_stock { 5 } else { 0 }),
+            url: String::new(),
+        };
+        let offers = [
+            offer("Trendyol", 105.0, true),
+            offer("n11", 95.0, false),
+            offer("Hepsiburada", 99.0, true),
+        ];
+        assert_eq!(best_offer(&offers).unwrap().source, "Hepsiburada");
+
+        let sold_out = [offer("Trendyol", 105.0, false), offer("n11", 95.0, false)];
+        assert_eq!(best_offer(&sold_out).unwrap().source, "n11");
+        assert!(best_offer(&[]).is_none());
+    }
+
+    #[test]
+    fn products_carry_their_best_offer() {
+        for product in get_products() {
+            let best = best_offer(&product.offers).unwrap();
+            assert_eq!(
+                (product.best_price, product.best_price_source.as_str()),
+                (best.price, best.source.as_str())
+            );
+            assert_eq!(product.in_stock, product.offers.iter().any(|o| o.in_stock));
+            let home = product
+                .offers
+                .iter()
+                .find(|o| o.source == product.source)
+                .unwrap();
+            assert_eq!(home.price, product.price);
+            assert!(product
+                .offers
+                .iter()
+                .all(|o| o.stock_count.is_none_or(|n| o.in_stock == (n > 0))));
+        }
+    }
+
+    #[test]
+    fn deals_compare_today_with_the_median_and_flag_hikes() {
+        let criteria = DealCriteria {
+            baseline_days: 7,
+            markup_window_days: 2,
+            ..DealCriteria::default()
+        };
+        let entry = &CATALOG[0];
+
+        // Median of the first seven days is 100; today is 10% below it.
+        let steady = series(&[100.0, 98.0, 102.0, 100.0, 99.0, 101.0, 100.0, 90.0]);
+        let deal = find_deal(entry, &steady, &criteria).unwrap();
+        assert_eq!((deal.baseline_price, deal.drop_percent), (100.0, 10.0));
+        assert_eq!(deal.recent_peak, 101.0);
+        assert!(!deal.suspected_fake_discount);
+
+        // A one-off spike barely moves the median but inflates the advertised drop.
+        let hiked = series(&[100.0, 98.0, 102.0, 100.0, 99.0, 130.0, 100.0, 90.0]);
+        let deal = find_deal(entry, &hiked, &criteria).unwrap();
+        assert_eq!((deal.baseline_price, deal.recent_peak), (100.0, 130.0));
+        assert_eq!(deal.advertised_drop_percent, 30.77);
+        assert!(deal.suspected_fake_discount);
+
+        // Outside the markup window the spike no longer counts.
+        let earlier = series(&[100.0, 98.0, 130.0, 100.0, 99.0, 101.0, 100.0, 90.0]);
+        assert!(
+            !find_deal(entry, &earlier, &criteria)
+                .unwrap()
+                .suspected_fake_discount
+        );
+
+        let shallow = series(&[100.0, 100.0, 100.0, 98.0]);
+        assert!(find_deal(entry, &shallow, &criteria).is_none());
+        assert!(find_deal(entry, &series(&[90.0]), &criteria).is_none());
+    }
+
+    #[test]
+    fn deals_are_ranked_by_drop() {
+        let deals = get_deals(&DealCriteria {
+            min_drop_percent: 0.0,
+            ..DealCriteria::default()
+        });
+        assert!(deals
+            .windows(2)
+            .all(|w| w[0].drop_percent >= w[1].drop_percent));
+        assert!(deals.iter().all(|d| d.drop_percent >= 0.0));
+    }
+
+    #[test]
+    fn every_endpoint_reads_the_same_catalogue() {
+        let products = get_products();
+        assert_eq!(products.len(), CATALOG.len());
+        for product in &products {
+            let single = get_product(&product.id).unwrap();
+            assert_eq!(
+                (single.name.as_str(), single.price),
+                (product.name.as_str(), product.price)
+            );
+            assert_eq!(single.price_history.len(), 30);
+            assert_eq!(single.price_history.last().unwrap().price, single.price);
+        }
+        let mut ids: Vec<_> = products.iter().map(|p| p.id.as_str()).collect();
+        ids.sort();
+        ids.dedup();
+        assert_eq!(ids.len(), CATALOG.len());
+        assert!(get_product("elec-999").is_none());
+        assert!(get_offers("elec-999").is_none());
+    }
+}
@@ -1,3 +1,4 @@
+pub mod cities;
 pub mod crypto;
 pub mod ecommerce;
 pub mod news;
@@ -1,26 +1,132 @@
-use chrono::Utc;
-use rand::seq::SliceRandom;
-use rand::Rng;
-use serde::Serialize;
+use chrono::{DateTime, TimeDelta, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::OnceLock;
 
-#[derive(Debug, Clone, Serialize)]
+use crate::analysis::entities::{self, Entity};
+use crate::analysis::{fnv1a, slug};
+
+/// Reading speed used to estimate `read_time_minutes`.
+const WORDS_PER_MINUTE: u32 = 200;
+
+/// The moment the mock feed was "generated"; article ages count back from it.
+const FEED_ANCHOR: &str = "2026-10-19T06:00:00Z";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NewsArticle {
     pub id: String,
     pub title: String,
     pub source: String,
-    pub published_at: String,
+    pub published_at: DateTime<Utc>,
     pub category: String,
     pub summary: String,
     pub url: String,
     pub author: String,
+    pub word_count: u32,
     pub read_time_minutes: u32,
     pub image_url: String,
+    pub keywords: Vec<String>,
+    pub entities: Vec<Entity>,
+}
+
+/// Criteria for narrowing the news feed. Text comparisons ignore case.
+#[derive(Debug, Clone, Default)]
+pub struct ArticleFilter {
+    pub category: Option<String>,
+    pub source: Option<String>,
+    /// Matches any author whose name contains this text.
+    pub author: Option<String>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+    /// Whitespace-separated terms that must all appear in the title or summary.
+    pub q: Option<String>,
+}
+
+impl ArticleFilter {
+    pub fn matches(&self, article: &NewsArticle) -> bool {
+        let haystack = format!("{} {}", article.title, article.summary).to_lowercase();
+
+        self.category
+            .as_ref()
+            .is_none_or(|c| article.category.eq_ignore_ascii_case(c))
+            && self
+                .source
+                .as_ref()
+                .is_none_or(|s| article.source.eq_ignore_ascii_case(s))
+            && self
+                .author
+                .as_ref()
+                .is_none_or(|a| article.author.to_lowercase().contains(&a.to_lowercase()))
+            && self.since.is_none_or(|since| article.published_at >= since)
+            && self.until.is_none_or(|until| article.published_at <= until)
+            && self.q.as_ref().is_none_or(|q| {
+                q.split_whitespace()
+                    .all(|term| haystack.contains(&term.to_lowercase()))
+            })
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CategoryCount {
+    pub category: String,
+    pub count: usize,
+}
+
+/// Count articles per category, most populated first.
+pub fn get_categories(articles: &[NewsArticle]) -> Vec<CategoryCount> {
+    let mut counts: Vec<CategoryCount> = Vec::new();
+    for article in articles {
+        match counts.iter_mut().find(|c| c.category == article.category) {
+            Some(entry) => entry.count += 1,
+            None => counts.push(CategoryCount {
+                category: article.category.clone(),
+                count: 1,
+            }),
+        }
+    }
+    counts.sort_by(|a, b| {
+        b.count
+            .cmp(&a.count)
+            .then_with(|| a.category.cmp(&b.category))
+    });
+    counts
 }
 
-/// Return 25 mock news articles with realistic timestamps.
+/// Return 31 mock news articles. Several events are covered by more than one
+/// outlet, as in a real aggregated feed.
+///
+/// Timestamps are fixed relative to `FEED_ANCHOR`, so IDs, publication times
+/// and pagination cursors are the same across calls and restarts.
 pub fn get_feed() -> Vec<NewsArticle> {
-    let mut rng = rand::thread_rng();
-    let now = Utc::now();
+    static FEED: OnceLock<Vec<NewsArticle>> = OnceLock::new();
+    FEED.get_or_init(generate_feed).clone()
+}
+
+/// Add the `articles` whose IDs are not in `feed` yet, returning how many.
+pub fn merge(feed: &mut Vec<NewsArticle>, articles: Vec<NewsArticle>) -> usize {
+    let mut seen: HashSet<String> = feed.iter().map(|a| a.id.clone()).collect();
+    let before = feed.len();
+    feed.extend(articles.into_iter().filter(|a| seen.insert(a.id.clone())));
+    feed.len() - before
+}
+
+/// Content-derived article ID, so the same story always gets the same ID.
+pub fn article_id(source: &str, title: &str, url: &str) -> String {
+    let content = format!("{source}\n{title}\n{url}");
+    format!("news-{:016x}", fnv1a(content.as_bytes()))
+}
+
+/// Minutes needed to read `word_count` words, at least one.
+pub fn read_time_minutes(word_count: u32) -> u32 {
+    word_count.div_ceil(WORDS_PER_MINUTE).max(1)
+}
+
+/// Build the mock feed. Each article is published 1 to 71 hours before
+/// `FEED_ANCHOR`, at an offset derived from its ID.
+fn generate_feed() -> Vec<NewsArticle> {
+    let anchor = DateTime::parse_from_rfc3339(FEED_ANCHOR)
+        .map(|t| t.with_timezone(&Utc))
+        .unwrap_or_default();
 
     let articles = vec![
         (
@@ -29,6 +135,7 @@ pub fn get_feed() -> Vec<NewsArticle> {
             "Technology",
             "World leaders reached consensus on AI safety protocols at the Geneva summit, establishing binding guidelines for frontier model development.",
             "Dr. Sarah Mitchell",
+            1240,
         ),
         (
             "European Central Bank Holds Rates Steady Amid Inflation Concerns",
@@ -36,6 +143,7 @@ pub fn get_feed() -> Vec<NewsArticle> {
             "Finance",
             "The ECB maintained its benchmark rate at 3.25%, citing persistent core inflation despite softening energy prices across the eurozone.",
             "James Crawford",
+            860,
         ),
         (
             "SpaceX Successfully Launches First Commercial Mars Cargo Mission",
@@ -43,6 +151,7 @@ pub fn get_feed() -> Vec<NewsArticle> {
             "Science",
             "The Starship Heavy lifted off from Boca Chica carrying 50 tonnes of pre-positioned supplies for the planned 2028 crewed Mars mission.",
             "Emily Zhang",
+            1120,
         ),
         (
             "Breakthrough Battery Technology Promises 1000-Mile EV Range",
@@ -50,6 +159,7 @@ pub fn get_feed() -> Vec<NewsArticle> {
             "Technology",
             "Researchers at Stanford unveiled a solid-state lithium-sulfur battery achieving 950 Wh/kg energy density, potentially revolutionizing electric vehicle range.",
             "Marcus Rivera",
+            980,
         ),
         (
             "Champions League Quarter-Final Draw Produces Dream Matchups",
@@ -57,6 +167,7 @@ pub fn get_feed() -> Vec<NewsArticle> {
             "Sports",
             "The UEFA Champions League draw paired Real Madrid against Manchester City and Bayern Munich against Barcelona in highly anticipated quarter-final ties.",
             "David O'Brien",
+            740,
         ),
         (
             "Major Cybersecurity Vulnerability Found in IoT Devices Worldwide",
@@ -64,6 +175,7 @@ pub fn get_feed() -> Vec<NewsArticle> {
             "Technology",
             "Security researchers disclosed a critical flaw affecting an estimated 2 billion connected devices, prompting emergency patches from major manufacturers.",
             "Alex Kowalski",
+            1310,
         ),
         (
             "UN Climate Report Warns of Accelerating Arctic Ice Loss",
@@ -71,6 +183,7 @@ pub fn get_feed() -> Vec<NewsArticle> {
             "Environment",
             "New satellite data reveals Arctic sea ice is declining 15% faster than previous models predicted, with potential ice-free summers by 2035.",
             "Dr. Rachel Green",
+            1050,
         ),
         (
             "Tokyo Stock Exchange Reaches All-Time High",
@@ -78,6 +191,7 @@ pub fn get_feed() -> Vec<NewsArticle> {
             "Finance",
             "The Nikkei 225 surged past 45,000 for the first time, driven by strong semiconductor earnings and a weakening yen boosting export competitiveness.",
             "Kenji Tanaka",
+            690,
         ),
         (
             "WHO Declares New Pandemic Preparedness Framework",
@@ -85,6 +199,7 @@ pub fn get_feed() -> Vec<NewsArticle> {
             "Health",
             "The World Health Organization launched a comprehensive early-warning system integrating genomic surveillance across 180 member nations.",
             "Lisa Andersen",
+            920,
         ),
         (
             "Quantum Computing Milestone: First Error-Corrected Calculation",
@@ -92,6 +207,7 @@ pub fn get_feed() -> Vec<NewsArticle> {
             "Science",
             "Google DeepMind achieved the first fully error-corrected quantum computation using 1,000 physical qubits, marking a pivotal step toward practical quantum advantage.",
             "Prof. Michael Chen",
+            1480,
         ),
         (
             "Global Shipping Disruptions Ease as Red Sea Tensions Subside",
@@ -99,6 +215,7 @@ pub fn get_feed() -> Vec<NewsArticle> {
             "Business",
             "Container shipping rates dropped 30% as major carriers resumed Red Sea transit routes following diplomatic breakthroughs in the region.",
             "Hannah Brooks",
+            810,
         ),
         (
             "India Surpasses China as World's Most Populous Nation by UN Metrics",
@@ -106,6 +223,7 @@ pub fn get_feed() -> Vec<NewsArticle> {
             "World",
             "Updated census data confirms India's population at 1.44 billion, with demographic shifts expected to reshape global economic dynamics over the coming decades.",
             "Priya Sharma",
+            1020,
         ),
         (
             "New CRISPR Therapy Shows Promise for Sickle Cell Disease",
@@ -113,6 +231,7 @@ pub fn get_feed() -> Vec<NewsArticle> {
             "Health",
             "Phase III clinical trial results demonstrated 94% efficacy in eliminating pain crises for sickle cell patients using a single-dose gene editing treatment.",
             "Dr. Thomas Wright",
+            1160,
         ),
         (
             "Renewable Energy Surpasses Fossil Fuels in EU Power Generation",
@@ -120,6 +239,7 @@ pub fn get_feed() -> Vec<NewsArticle> {
             "Environment",
             "For the first time, wind and solar generated more electricity than coal and gas combined across the European Union in a full calendar year.",
             "Marie Dubois",
+            870,
         ),
         (
             "Hollywood Actors Ratify New AI Likeness Protection Agreement",
@@ -127,6 +247,7 @@ pub fn get_feed() -> Vec<NewsArticle> {
             "Entertainment",
             "SAG-AFTRA members overwhelmingly approved a contract establishing strict consent and compensation frameworks for AI-generated performances.",
             "Jordan Hayes",
+            760,
         ),
         (
             "Central African Mining Deal Sparks International Resource Debate",
@@ -134,6 +255,7 @@ pub fn get_feed() -> Vec<NewsArticle> {
             "Business",
             "A $12 billion rare-earth mining agreement between the DRC and a consortium of Asian firms reignited discussions about resource sovereignty.",
             "Robert Okafor",
+            1390,
         ),
         (
             "Self-Driving Taxi Services Expand to 15 New US Cities",
@@ -141,6 +263,7 @@ pub fn get_feed() -> Vec<NewsArticle> {
             "Technology",
             "Waymo and Cruise announced simultaneous expansions, bringing autonomous ride-hailing to medium-sized cities following updated federal safety frameworks.",
             "Sophia Martinez",
+            940,
         ),
         (
             "Global Wheat Prices Surge After Australian Drought Worsens",
@@ -148,6 +271,7 @@ pub fn get_feed() -> Vec<NewsArticle> {
             "Finance",
             "Commodity markets reacted sharply as Australia's Bureau of Meteorology downgraded harvest projections by 40%, threatening global food supply chains.",
             "Peter Hennessy",
+            720,
         ),
         (
             "Ancient Roman City Discovered Beneath Istanbul Construction Site",
@@ -155,6 +279,7 @@ pub fn get_feed() -> Vec<NewsArticle> {
             "Culture",
             "Archaeologists uncovered a remarkably preserved Roman settlement dating to the 3rd century AD during metro expansion excavations near the Golden Horn.",
             "Dr. Elif Yilmaz",
+            1080,
         ),
         (
             "World's Largest Offshore Wind Farm Begins Operations in North Sea",
@@ -162,6 +287,7 @@ pub fn get_feed() -> Vec<NewsArticle> {
             "Environment",
             "The 4.1 GW Dogger Bank wind farm achieved full operational capacity, capable of powering 6 million UK homes with clean energy.",
             "Oliver Walsh",
+            830,
         ),
         (
             "Federal Reserve Signals Potential Rate Cuts in Q2 2026",
@@ -169,6 +295,7 @@ pub fn get_feed() -> Vec<NewsArticle> {
             "Finance",
             "Fed Chair's testimony indicated growing confidence in inflation trends, with markets now pricing in three 25-basis-point cuts beginning in April.",
             "Catherine Blake",
+            900,
         ),
         (
             "Major Social Media Platforms Implement Age Verification Systems",
@@ -176,6 +303,7 @@ pub fn get_feed() -> Vec<NewsArticle> {
             "Technology",
             "Meta, TikTok, and X rolled out mandatory identity verification for users under 18, complying with new EU and US digital safety regulations.",
             "Tyler Kim",
+            1010,
         ),
         (
             "Olympic Committee Announces Esports Exhibition Events for 2028 LA Games",
@@ -183,6 +311,7 @@ pub fn get_feed() -> Vec<NewsArticle> {
             "Sports",
             "Five competitive gaming titles will be featured as exhibition events at the 2028 Los Angeles Olympics, marking a historic step for competitive gaming.",
             "Ryan Patel",
+            650,
         ),
         (
             "Global Microchip Shortage Finally Easing, Industry Reports Show",
@@ -190,6 +319,7 @@ pub fn get_feed() -> Vec<NewsArticle> {
             "Technology",
             "Leading foundries reported utilization rates returning to normal levels as new fabrication facilities in the US, Japan, and EU reach production capacity.",
             "Dr. Hiro Nakamura",
+            880,
         ),
         (
             "Record-Breaking Coral Reef Recovery Observed in Great Barrier Reef",
@@ -197,43 +327,109 @@ pub fn get_feed() -> Vec<NewsArticle> {
             "Environment",
             "Marine biologists documented unprecedented coral growth across 60% of surveyed sites, attributed to coordinated conservation efforts and cooler-than-expected water temperatures.",
             "Dr. Claire Bennett",
+            1190,
+        ),
+        (
+            "ECB Holds Rates Steady as Inflation Concerns Persist",
+            "Reuters",
+            "Finance",
+            "The European Central Bank kept its benchmark rate at 3.25%, citing persistent core inflation even as energy prices softened across the eurozone.",
+            "Anna Keller",
+            610,
+        ),
+        (
+            "SpaceX Launches First Commercial Cargo Mission to Mars",
+            "BBC News",
+            "Science",
+            "SpaceX's Starship Heavy lifted off from Boca Chica carrying 50 tonnes of pre-positioned supplies ahead of the planned 2028 crewed Mars mission.",
+            "Jonathan Amos",
+            770,
+        ),
+        (
+            "Global AI Summit Ends with Landmark Agreement on AI Safety",
+            "Associated Press",
+            "Technology",
+            "World leaders at the Geneva summit reached consensus on AI safety protocols, establishing binding guidelines for the development of frontier models.",
+            "Frank Jordans",
+            700,
+        ),
+        (
+            "AI Summit in Geneva Concludes with Landmark Safety Agreement",
+            "Euronews",
+            "Technology",
+            "Leaders reached a consensus on AI safety protocols at the Geneva summit, setting binding guidelines for frontier model development.",
+            "Marie Dubois",
+            580,
+        ),
+        (
+            "Critical Cybersecurity Vulnerability Found in Billions of IoT Devices",
+            "TechCrunch",
+            "Technology",
+            "Security researchers disclosed a critical flaw affecting an estimated 2 billion connected devices worldwide, prompting emergency patches from major manufacturers.",
+            "Zack Whittaker",
+            1220,
+        ),
+        (
+            "Bitcoin Climbs Past $100,000 as Spot ETF Inflows Accelerate",
+            "CoinDesk",
+            "Finance",
+            "Bitcoin topped $100,000 as spot ETFs from BlackRock and Fidelity drew record inflows, with Ethereum and Solana rising alongside BTC.",
+            "Nadia Rahman",
+            720,
         ),
     ];
 
     articles
         .into_iter()
         .enumerate()
-        .map(|(i, (title, source, category, summary, author))| {
-            let hours_ago = rng.gen_range(1..72);
-            let published = now - chrono::Duration::hours(hours_ago);
-
-            NewsArticle {
-                id: format!("news-{:03}", i + 1),
-                title: title.to_string(),
-                source: source.to_string(),
-                published_at: published.to_rfc3339(),
-                category: category.to_string(),
-                summary: summary.to_string(),
-                url: format!(
+        .map(
+            |(i, (title, source, category, summary, author, word_count))| {
+                let url = format!(
                     "https://{}.com/articles/{}",
                     source.to_lowercase().replace(' ', ""),
                     slug(title)
-                ),
-                author: author.to_string(),
-                read_time_minutes: *[3u32, 4, 5, 6, 7, 8].choose(&mut rng).unwrap(),
-                image_url: format!("https://picsum.photos/seed/{}/800/400", i + 100),
-            }
-        })
+                );
+                let id = article_id(source, title, &url);
+                let hours_ago = 1 + fnv1a(id.as_bytes()) % 71;
+                let published = anchor - TimeDelta::hours(hours_ago as i64);
+
+                let mut article = NewsArticle {
+                    id,
+                    title: title.to_string(),
+                    source: source.to_string(),
+                    published_at: published,
+                    category: category.to_string(),
+                    summary: summary.to_string(),
+                    url,
+                    author: author.to_string(),
+                    word_count,
+                    read_time_minutes: read_time_minutes(word_count),
+                    image_url: format!("https://picsum.photos/seed/{}/800/400", i + 100),
+                    keywords: Vec::new(),
+                    entities: Vec::new(),
+                };
+                entities::enrich(&mut article);
+                article
+            },
+        )
         .collect()
 }
 
-fn slug(s: &str) -> String {
-    s.to_lowercase()
-        .chars()
-        .map(|c| if c.is_alphanumeric() { c } else { '-' })
-        .collect::<String>()
-        .split('-')
-        .filter(|p| !p.is_empty())
-        .collect::<Vec<_>>()
-        .join("-")
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merges_only_articles_not_in_the_feed() {
+        let mut feed = get_feed();
+        let total = feed.len();
+        let mut fresh = feed[0].clone();
+        fresh.id = article_id("Example Wire", "Something new", "https://example.com/new");
+        let known = feed[1].clone();
+        let added = merge(&mut feed, vec![known, fresh.clone(), fresh]);
+        assert_eq!(added, 1);
+        assert_eq!(feed.len(), total + 1);
+        let counted: usize = get_categories(&feed).iter().map(|c| c.count).sum();
+        assert_eq!(counted, total + 1);
+    }
 }
@@ -1,6 +1,25 @@
-use chrono::Utc;
-use rand::Rng;
-use serde::Serialize;
+use chrono::{DateTime, DurationRound, TimeDelta, Timelike, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::cooccurrence::CoOccurrence;
+use crate::analysis::sentiment::{self, SentimentLabel};
+use crate::analysis::trends::{self, Bucket, Momentum};
+
+/// Hourly points returned by [`get_sentiment`].
+const SENTIMENT_WINDOW_HOURS: i64 = 24;
+
+/// Hashtags used in fewer posts than this are not reported as trends.
+const MIN_TREND_MENTIONS: usize = 10;
+
+/// Most trends returned by [`get_trends`].
+const MAX_TRENDS: usize = 20;
+
+/// A topic has been trending since the first hour in which it drew at least
+/// this fraction of its busiest hour's mentions.
+const TRENDING_FRACTION: f64 = 0.25;
+
+/// Posts two hashtags must share before [`get_graph`] links them.
+const MIN_EDGE_WEIGHT: u64 = 2;
 
 #[derive(Debug, Clone, Serialize)]
 pub struct TrendingTopic {
@@ -8,10 +27,57 @@ pub struct TrendingTopic {
     pub name: String,
     pub mention_count: u64,
     pub sentiment: SentimentBreakdown,
+    /// The platform with the most mentions.
     pub platform: String,
+    pub platforms: Vec<PlatformStats>,
     pub hashtag: String,
     pub peak_hour: String,
     pub trending_since: String,
+    pub momentum: Momentum,
+}
+
+/// Mentions, sentiment and engagement on one platform.
+#[derive(Debug, Clone, Serialize)]
+pub struct PlatformStats {
+    pub platform: String,
+    pub mention_count: u64,
+    /// Percentage of all the posts summarized.
+    pub share: f64,
+    pub sentiment: SentimentBreakdown,
+    pub engagement: Engagement,
+    /// Likes, shares and replies per post.
+    pub engagement_per_post: f64,
+}
+
+/// How a topic, or every post, performs on each platform.
+#[derive(Debug, Clone, Serialize)]
+pub struct PlatformSummary {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub topic: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hashtag: Option<String>,
+    pub total_mentions: u64,
+    pub platforms: Vec<PlatformStats>,
+}
+
+/// How [`get_trends`] orders topics.
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TrendSort {
+    /// Most mentioned first.
+    #[default]
+    Mentions,
+    /// Fastest growing mention rate first.
+    Velocity,
+}
+
+/// A topic whose mention rate has jumped well above its own history, with
+/// the hourly counts that show it.
+#[derive(Debug, Clone, Serialize)]
+pub struct EmergingTopic {
+    #[serde(flatten)]
+    pub trend: TrendingTopic,
+    pub buckets: Vec<Bucket>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -24,6 +90,8 @@ pub struct SentimentBreakdown {
 #[derive(Debug, Clone, Serialize)]
 pub struct SentimentOverTime {
     pub topic: String,
+    pub name: String,
+    pub hashtag: String,
     pub data_points: Vec<SentimentDataPoint>,
     pub overall: SentimentBreakdown,
     pub total_mentions: u64,
@@ -38,86 +106,562 @@ pub struct SentimentDataPoint {
     pub mention_count: u64,
 }
 
-/// Return 15 mock trending topics.
-pub fn get_trends() -> Vec<TrendingTopic> {
-    let mut rng = rand::thread_rng();
-    let now = Utc::now();
-
-    let topics = vec![
-        ("tech-ai", "AI Regulation", 124500, 42.0, 28.0, 30.0, "Twitter", "#AIRegulation"),
-        ("tech-quantum", "Quantum Computing", 89200, 61.0, 12.0, 27.0, "Twitter", "#QuantumComputing"),
-        ("sports-ucl", "Champions League", 312000, 55.0, 20.0, 25.0, "Twitter", "#UCL"),
-        ("gaming-gta", "GTA VI Trailer", 567000, 72.0, 8.0, 20.0, "Reddit", "#GTAVI"),
-        ("music-grammy", "Grammy Awards 2026", 234000, 48.0, 32.0, 20.0, "Instagram", "#Grammys"),
-        ("finance-btc", "Bitcoin Rally", 178000, 65.0, 18.0, 17.0, "Twitter", "#Bitcoin"),
-        ("health-mental", "Mental Health Awareness", 91200, 58.0, 15.0, 27.0, "Instagram", "#MentalHealth"),
-        ("climate-cop", "COP31 Summit", 67800, 35.0, 40.0, 25.0, "Twitter", "#COP31"),
-        ("food-vegan", "Veganuary Results", 45600, 52.0, 22.0, 26.0, "Instagram", "#Veganuary"),
-        ("space-mars", "Mars Mission Update", 156000, 78.0, 5.0, 17.0, "Reddit", "#MarsUpdate"),
-        ("fashion-week", "Paris Fashion Week", 203000, 62.0, 14.0, 24.0, "Instagram", "#PFW"),
-        ("politics-election", "EU Policy Reform", 134000, 30.0, 45.0, 25.0, "Twitter", "#EUReform"),
-        ("education-remote", "Remote Learning Stats", 56700, 40.0, 35.0, 25.0, "Reddit", "#RemoteLearning"),
-        ("auto-ev", "EV Battery Breakthrough", 98400, 70.0, 10.0, 20.0, "Twitter", "#EVBattery"),
-        ("social-threads", "Threads vs Twitter", 189000, 38.0, 34.0, 28.0, "Reddit", "#ThreadsVsTwitter"),
-    ];
-
-    topics
-        .into_iter()
-        .enumerate()
-        .map(|(i, (id, name, mentions, pos, neg, neu, platform, hashtag))| {
-            let variation = rng.gen_range(0.9..1.1);
-            let trending_since = now - chrono::Duration::hours(rng.gen_range(2..48));
+/// A post exported from a social platform, with the hashtags and mentions
+/// found in its text.
+#[derive(Debug, Clone, Serialize)]
+pub struct SocialPost {
+    pub id: String,
+    pub platform: String,
+    pub author: String,
+    pub text: String,
+    pub timestamp: DateTime<Utc>,
+    pub engagement: Engagement,
+    pub hashtags: Vec<String>,
+    pub mentions: Vec<String>,
+}
 
-            TrendingTopic {
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Engagement {
+    pub likes: u64,
+    pub shares: u64,
+    pub replies: u64,
+}
+
+/// The end of the hour containing the newest post; analysis windows end here
+/// so results stay the same for as long as the corpus does.
+pub fn as_of(posts: &[SocialPost]) -> DateTime<Utc> {
+    let newest = posts
+        .iter()
+        .map(|p| p.timestamp)
+        .max()
+        .unwrap_or_else(Utc::now);
+    newest
+        .duration_trunc(TimeDelta::hours(1))
+        .map(|hour| hour + TimeDelta::hours(1))
+        .unwrap_or(newest)
+}
+
+/// Display names for known hashtags: id, name, hashtag. Other hashtags are
+/// reported under their own spelling.
+const TOPICS: &[(&str, &str, &str)] = &[
+    ("tech-ai", "AI Regulation", "#AIRegulation"),
+    ("tech-quantum", "Quantum Computing", "#QuantumComputing"),
+    ("sports-ucl", "Champions League", "#UCL"),
+    ("gaming-gta", "GTA VI Trailer", "#GTAVI"),
+    ("music-grammy", "Grammy Awards 2026", "#Grammys"),
+    ("finance-btc", "Bitcoin Rally", "#Bitcoin"),
+    ("health-mental", "Mental Health Awareness", "#MentalHealth"),
+    ("climate-cop", "COP31 Summit", "#COP31"),
+    ("food-vegan", "Veganuary Results", "#Veganuary"),
+    ("space-mars", "Mars Mission Update", "#MarsUpdate"),
+    ("fashion-week", "Paris Fashion Week", "#PFW"),
+    ("politics-election", "EU Policy Reform", "#EUReform"),
+    ("education-remote", "Remote Learning Stats", "#RemoteLearning"),
+    ("auto-ev", "EV Battery Breakthrough", "#EVBattery"),
+    ("social-threads", "Threads vs Twitter", "#ThreadsVsTwitter"),
+];
+
+/// A topic, identified by the hashtag whose posts it covers.
+#[derive(Debug, Clone)]
+pub struct Topic {
+    pub id: String,
+    pub name: String,
+    pub hashtag: String,
+}
+
+impl Topic {
+    /// The topic for a hashtag: its registry entry if known, otherwise an id
+    /// of the form `tag-bitcoin` and the hashtag itself as the name.
+    pub fn for_hashtag(hashtag: &str) -> Self {
+        match TOPICS
+            .iter()
+            .find(|(_, _, known)| known.eq_ignore_ascii_case(hashtag))
+        {
+            Some(&(id, name, known)) => Self {
                 id: id.to_string(),
                 name: name.to_string(),
-                mention_count: (mentions as f64 * variation) as u64,
-                sentiment: SentimentBreakdown {
-                    positive: (pos as f64 + rng.gen_range(-3.0_f64..3.0)).clamp(0.0, 100.0),
-                    negative: (neg as f64 + rng.gen_range(-3.0_f64..3.0)).clamp(0.0, 100.0),
-                    neutral: (neu as f64 + rng.gen_range(-3.0_f64..3.0)).clamp(0.0, 100.0),
-                },
-                platform: platform.to_string(),
+                hashtag: known.to_string(),
+            },
+            None => Self {
+                id: format!("tag-{}", hashtag.trim_start_matches('#').to_lowercase()),
+                name: hashtag.trim_start_matches('#').to_string(),
                 hashtag: hashtag.to_string(),
-                peak_hour: format!("{:02}:00 UTC", (10 + i * 2) % 24),
-                trending_since: trending_since.to_rfc3339(),
+            },
+        }
+    }
+
+    /// Whether `post` is tagged with this topic's hashtag.
+    pub fn covers(&self, post: &SocialPost) -> bool {
+        post.hashtags
+            .iter()
+            .any(|tag| tag.to_lowercase() == self.hashtag.to_lowercase())
+    }
+}
+
+/// Find a topic by id (`finance-btc`, `tag-crypto`), hashtag with or without
+/// the `#` (`#Bitcoin`, `bitcoin`) or registry name (`Bitcoin Rally`),
+/// ignoring case. Hashtags outside the registry resolve only if some post
+/// uses them.
+pub fn resolve_topic(posts: &[SocialPost], query: &str) -> Option<Topic> {
+    let query = query.trim().to_lowercase();
+    let bare = query.trim_start_matches('#');
+    if let Some(&(_, _, hashtag)) = TOPICS.iter().find(|(id, name, hashtag)| {
+        id.to_lowercase() == query
+            || name.to_lowercase() == query
+            || hashtag[1..].to_lowercase() == bare
+    }) {
+        return Some(Topic::for_hashtag(hashtag));
+    }
+    let bare = bare.strip_prefix("tag-").unwrap_or(bare);
+    posts
+        .iter()
+        .flat_map(|p| &p.hashtags)
+        .find(|tag| tag[1..].to_lowercase() == bare)
+        .map(|tag| Topic::for_hashtag(tag))
+}
+
+/// Group posts by hashtag, ignoring case, keeping each tag's first spelling.
+fn by_hashtag<'a>(
+    posts: impl IntoIterator<Item = &'a SocialPost>,
+) -> Vec<(String, Vec<&'a SocialPost>)> {
+    let mut by_tag: Vec<(String, Vec<&SocialPost>)> = Vec::new();
+    for post in posts {
+        for tag in &post.hashtags {
+            match by_tag
+                .iter_mut()
+                .find(|(known, _)| known.to_lowercase() == tag.to_lowercase())
+            {
+                Some((_, tagged)) => tagged.push(post),
+                None => by_tag.push((tag.clone(), vec![post])),
+            }
+        }
+    }
+    by_tag
+}
+
+/// Aggregate posts into trending topics, one per hashtag used in at least
+/// `MIN_TREND_MENTIONS` posts. The `MAX_TRENDS` most mentioned are kept and
+/// returned in `sort` order. With `platform`, only that platform's posts
+/// are counted.
+pub fn get_trends(
+    posts: &[SocialPost],
+    sort: TrendSort,
+    platform: Option<&str>,
+) -> Vec<TrendingTopic> {
+    let end = as_of(posts);
+    let selected = posts
+        .iter()
+        .filter(|p| platform.is_none_or(|platform| p.platform.eq_ignore_ascii_case(platform)));
+    let mut trends: Vec<TrendingTopic> = by_hashtag(selected)
+        .into_iter()
+        .filter(|(_, tagged)| tagged.len() >= MIN_TREND_MENTIONS)
+        .map(|(tag, tagged)| trend(Topic::for_hashtag(&tag), &tagged, end).0)
+        .collect();
+    trends.sort_by(|a, b| {
+        b.mention_count
+            .cmp(&a.mention_count)
+            .then_with(|| a.id.cmp(&b.id))
+    });
+    trends.truncate(MAX_TRENDS);
+    if let TrendSort::Velocity = sort {
+        trends.sort_by(|a, b| {
+            b.momentum
+                .velocity
+                .total_cmp(&a.momentum.velocity)
+                .then_with(|| a.id.cmp(&b.id))
+        });
+    }
+    trends
+}
+
+/// Every hashtag whose latest mention rate is anomalously high, however few
+/// mentions it has in total, most anomalous first.
+pub fn get_emerging(posts: &[SocialPost]) -> Vec<EmergingTopic> {
+    let end = as_of(posts);
+    let mut emerging: Vec<EmergingTopic> = by_hashtag(posts)
+        .into_iter()
+        .map(|(tag, tagged)| trend(Topic::for_hashtag(&tag), &tagged, end))
+        .filter(|(trend, _)| trend.momentum.emerging)
+        .map(|(trend, counts)| EmergingTopic {
+            trend,
+            buckets: trends::buckets(&counts, end),
+        })
+        .collect();
+    emerging.sort_by(|a, b| {
+        b.trend
+            .momentum
+            .z_score
+            .total_cmp(&a.trend.momentum.z_score)
+            .then_with(|| a.trend.id.cmp(&b.trend.id))
+    });
+    emerging
+}
+
+/// Per-platform stats for a topic's posts, or for every post without one.
+pub fn get_platforms(posts: &[SocialPost], topic: Option<&Topic>) -> PlatformSummary {
+    let selected: Vec<&SocialPost> = posts
+        .iter()
+        .filter(|p| topic.is_none_or(|topic| topic.covers(p)))
+        .collect();
+    PlatformSummary {
+        topic: topic.map(|t| t.id.clone()),
+        hashtag: topic.map(|t| t.hashtag.clone()),
+        total_mentions: selected.len() as u64,
+        platforms: platform_stats(&selected),
+    }
+}
+
+/// Stats per platform, most mentions first.
+fn platform_stats(posts: &[&SocialPost]) -> Vec<PlatformStats> {
+    let mut by_platform: Vec<(&str, Vec<&SocialPost>)> = Vec::new();
+    for &post in posts {
+        match by_platform.iter_mut().find(|(p, _)| *p == post.platform) {
+            Some((_, on_platform)) => on_platform.push(post),
+            None => by_platform.push((&post.platform, vec![post])),
+        }
+    }
+
+    let mut stats: Vec<PlatformStats> = by_platform
+        .into_iter()
+        .map(|(platform, on_platform)| {
+            let engagement = on_platform
+                .iter()
+                .fold(Engagement::default(), |sum, p| Engagement {
+                    likes: sum.likes + p.engagement.likes,
+                    shares: sum.shares + p.engagement.shares,
+                    replies: sum.replies + p.engagement.replies,
+                });
+            let count = on_platform.len() as f64;
+            let interactions = (engagement.likes + engagement.shares + engagement.replies) as f64;
+            PlatformStats {
+                platform: platform.to_string(),
+                mention_count: on_platform.len() as u64,
+                share: (count / posts.len() as f64 * 1000.0).round() / 10.0,
+                sentiment: sentiment::breakdown(
+                    on_platform.iter().map(|p| sentiment::score(&p.text).label),
+                ),
+                engagement,
+                engagement_per_post: (interactions / count * 10.0).round() / 10.0,
             }
         })
-        .collect()
+        .collect();
+    stats.sort_by(|a, b| {
+        b.mention_count
+            .cmp(&a.mention_count)
+            .then_with(|| a.platform.cmp(&b.platform))
+    });
+    stats
+}
+
+/// Summarize a topic's posts, returning the hourly counts it was built from.
+fn trend(topic: Topic, tagged: &[&SocialPost], end: DateTime<Utc>) -> (TrendingTopic, Vec<u64>) {
+    let platforms = platform_stats(tagged);
+
+    let counts = trends::hourly_counts(tagged.iter().map(|p| p.timestamp), end);
+    let trend = TrendingTopic {
+        sentiment: sentiment::breakdown(tagged.iter().map(|p| sentiment::score(&p.text).label)),
+        id: topic.id,
+        name: topic.name,
+        mention_count: tagged.len() as u64,
+        platform: platforms
+            .first()
+            .map(|p| p.platform.clone())
+            .unwrap_or_default(),
+        platforms,
+        hashtag: topic.hashtag,
+        peak_hour: format!("{:02}:00 UTC", trends::peak(&counts, end).hour()),
+        trending_since: trends::first_reaching(&counts, end, TRENDING_FRACTION).to_rfc3339(),
+        momentum: trends::momentum(&counts),
+    };
+    (trend, counts)
+}
+
+/// Hashtags that appear together, as plain node and edge lists that graph
+/// libraries (d3-force, Cytoscape, vis-network) can load directly.
+#[derive(Debug, Clone, Serialize)]
+pub struct TopicGraph {
+    pub nodes: Vec<GraphNode>,
+    pub edges: Vec<GraphEdge>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GraphNode {
+    /// The topic id, as used by `/api/social/sentiment/{topic}`.
+    pub id: String,
+    pub label: String,
+    pub hashtag: String,
+    pub mentions: u64,
+    /// Hops from the requested topic; absent for the trending overview.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub depth: Option<usize>,
+}
+
+/// An undirected edge weighted by the number of posts tagged with both ends.
+#[derive(Debug, Clone, Serialize)]
+pub struct GraphEdge {
+    pub source: String,
+    pub target: String,
+    pub weight: u64,
 }
 
-/// Return detailed sentiment-over-time for a given topic.
-pub fn get_sentiment(topic: &str) -> SentimentOverTime {
-    let mut rng = rand::thread_rng();
-    let now = Utc::now();
+/// Build the hashtag co-occurrence graph. Around `topic` it holds every
+/// hashtag within `depth` hops; without one, the current trends. Only pairs
+/// sharing at least `MIN_EDGE_WEIGHT` posts are linked.
+pub fn get_graph(posts: &[SocialPost], topic: Option<&Topic>, depth: usize) -> TopicGraph {
+    let graph = CoOccurrence::build(
+        posts
+            .iter()
+            .map(|p| p.hashtags.iter().map(|tag| tag.to_lowercase())),
+    );
+    let members: Vec<(String, Option<usize>)> = match topic {
+        Some(topic) => graph
+            .reachable(&topic.hashtag.to_lowercase(), depth, MIN_EDGE_WEIGHT)
+            .into_iter()
+            .map(|(label, hops)| (label, Some(hops)))
+            .collect(),
+        None => get_trends(posts, TrendSort::Mentions, None)
+            .into_iter()
+            .map(|trend| (trend.hashtag.to_lowercase(), None))
+            .collect(),
+    };
+
+    let topics: Vec<Topic> = members
+        .iter()
+        .map(|(label, _)| {
+            let spelling = posts
+                .iter()
+                .flat_map(|p| &p.hashtags)
+                .find(|tag| tag.to_lowercase() == *label)
+                .map_or(label.as_str(), String::as_str);
+            Topic::for_hashtag(spelling)
+        })
+        .collect();
+    let labels: Vec<String> = members.iter().map(|(label, _)| label.clone()).collect();
+    let id_of = |label: &str| {
+        labels
+            .iter()
+            .position(|l| l == label)
+            .map(|i| topics[i].id.clone())
+            .unwrap_or_default()
+    };
+
+    TopicGraph {
+        edges: graph
+            .edges_among(&labels, MIN_EDGE_WEIGHT)
+            .into_iter()
+            .map(|(a, b, weight)| GraphEdge {
+                source: id_of(&a),
+                target: id_of(&b),
+                weight,
+            })
+            .collect(),
+        nodes: members
+            .iter()
+            .zip(&topics)
+            .map(|((label, depth), topic)| GraphNode {
+                id: topic.id.clone(),
+                label: topic.name.clone(),
+                hashtag: topic.hashtag.clone(),
+                mentions: graph.occurrences(label),
+                depth: *depth,
+            })
+            .collect(),
+    }
+}
 
-    let base_positive: f64 = rng.gen_range(35.0..65.0);
-    let base_negative: f64 = rng.gen_range(10.0..35.0);
-    let base_neutral: f64 = 100.0 - base_positive - base_negative;
+/// Score every post tagged with the topic's hashtag and return hourly
+/// sentiment for the last 24 hours of the corpus. `overall` and
+/// `total_mentions` cover every tagged post, so they match the topic's
+/// `TrendingTopic::sentiment` and `mention_count`.
+pub fn get_sentiment(posts: &[SocialPost], topic: &Topic) -> SentimentOverTime {
+    let scored: Vec<(DateTime<Utc>, SentimentLabel)> = posts
+        .iter()
+        .filter(|p| topic.covers(p))
+        .map(|p| (p.timestamp, sentiment::score(&p.text).label))
+        .collect();
 
-    let data_points: Vec<SentimentDataPoint> = (0..24)
+    let end = as_of(posts);
+    let data_points: Vec<SentimentDataPoint> = (0..SENTIMENT_WINDOW_HOURS)
         .map(|i| {
-            let timestamp = now - chrono::Duration::hours(23 - i);
+            let start = end - TimeDelta::hours(SENTIMENT_WINDOW_HOURS - i);
+            let labels: Vec<SentimentLabel> = scored
+                .iter()
+                .filter(|(t, _)| *t >= start && *t < start + TimeDelta::hours(1))
+                .map(|&(_, label)| label)
+                .collect();
+            let hour = sentiment::breakdown(labels.iter().copied());
             SentimentDataPoint {
-                timestamp: timestamp.to_rfc3339(),
-                positive: (base_positive + rng.gen_range(-8.0_f64..8.0)).clamp(0.0, 100.0),
-                negative: (base_negative + rng.gen_range(-5.0_f64..5.0)).clamp(0.0, 100.0),
-                neutral: (base_neutral + rng.gen_range(-5.0_f64..5.0)).clamp(0.0, 100.0),
-                mention_count: rng.gen_range(500..5000),
+                timestamp: start.to_rfc3339(),
+                positive: hour.positive,
+                negative: hour.negative,
+                neutral: hour.neutral,
+                mention_count: labels.len() as u64,
             }
         })
         .collect();
 
-    let total_mentions: u64 = data_points.iter().map(|d| d.mention_count).sum();
-
     SentimentOverTime {
-        topic: topic.to_string(),
+        topic: topic.id.clone(),
+        name: topic.name.clone(),
+        hashtag: topic.hashtag.clone(),
         data_points,
-        overall: SentimentBreakdown {
-            positive: (base_positive * 10.0).round() / 10.0,
-            negative: (base_negative * 10.0).round() / 10.0,
-            neutral: (base_neutral * 10.0).round() / 10.0,
-        },
-        total_mentions,
+        overall: sentiment::breakdown(scored.iter().map(|&(_, label)| label)),
+        total_mentions: scored.len() as u64,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extract::posts;
+
+    fn post(id: &str, platform: &str, minute: u32, text: &str) -> SocialPost {
+        SocialPost {
+            id: id.to_string(),
+            platform: platform.to_string(),
+            author: "@someone".to_string(),
+            text: text.to_string(),
+            timestamp: format!("2026-10-19T{:02}:{:02}:00Z", 8 + minute / 60, minute % 60)
+                .parse()
+                .unwrap(),
+            engagement: Engagement {
+                likes: 10,
+                shares: 2,
+                replies: 1,
+            },
+            hashtags: posts::hashtags(text),
+            mentions: posts::mentions(text),
+        }
+    }
+
+    #[test]
+    fn resolves_topics_by_id_hashtag_or_name() {
+        let posts = vec![post("1", "Reddit", 0, "Shipping it #RustLang")];
+        for query in ["finance-btc", "#bitcoin", "BITCOIN", " Bitcoin Rally "] {
+            let topic = resolve_topic(&posts, query).unwrap();
+            assert_eq!(
+                (topic.id.as_str(), topic.hashtag.as_str()),
+                ("finance-btc", "#Bitcoin")
+            );
+        }
+
+        // Tags outside the registry resolve only when a post uses them.
+        for query in ["rustlang", "#RUSTLANG", "tag-rustlang"] {
+            let topic = resolve_topic(&posts, query).unwrap();
+            assert_eq!(
+                (topic.id.as_str(), topic.name.as_str()),
+                ("tag-rustlang", "RustLang")
+            );
+        }
+        assert!(resolve_topic(&posts, "#NeverPosted").is_none());
+        assert!(resolve_topic(&posts, "tag-neverposted").is_none());
+    }
+
+    #[test]
+    fn sentiment_covers_every_tagged_post() {
+        let posts = vec![
+            post("1", "Reddit", 0, "Love this, amazing rally #Bitcoin"),
+            post("2", "X", 30, "Terrible crash, awful #bitcoin"),
+            post("3", "X", 90, "Just a chart #BITCOIN"),
+            post("4", "X", 95, "Unrelated #Mars"),
+        ];
+        let topic = resolve_topic(&posts, "finance-btc").unwrap();
+        let sentiment = get_sentiment(&posts, &topic);
+        assert_eq!(sentiment.topic, "finance-btc");
+        assert_eq!(sentiment.total_mentions, 3);
+        assert_eq!(sentiment.data_points.len(), SENTIMENT_WINDOW_HOURS as usize);
+        // The window ends at the top of the hour after the newest post.
+        assert_eq!(
+            sentiment.data_points.last().unwrap().timestamp,
+            "2026-10-19T09:00:00+00:00"
+        );
+        let counts: Vec<u64> = sentiment
+            .data_points
+            .iter()
+            .map(|p| p.mention_count)
+            .collect();
+        assert_eq!(counts[22..], [2, 1]);
+        let overall = &sentiment.overall;
+        assert!(overall.positive > 0.0 && overall.negative > 0.0);
+    }
+
+    #[test]
+    fn graph_follows_edges_of_minimum_weight() {
+        let posts = vec![
+            post("1", "X", 0, "#Mars #Starship"),
+            post("2", "X", 1, "#mars #starship"),
+            post("3", "X", 2, "#Starship #SpaceX"),
+            post("4", "X", 3, "#Starship #SpaceX"),
+            // Seen together once, below MIN_EDGE_WEIGHT.
+            post("5", "X", 4, "#SpaceX #Tesla"),
+        ];
+        let mars = resolve_topic(&posts, "mars").unwrap();
+        let nodes = |depth| {
+            get_graph(&posts, Some(&mars), depth)
+                .nodes
+                .into_iter()
+                .map(|n| (n.hashtag, n.depth))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            nodes(1),
+            vec![
+                ("#Mars".to_string(), Some(0)),
+                ("#Starship".to_string(), Some(1))
+            ]
+        );
+        assert_eq!(nodes(3).len(), 3);
+        assert!(!nodes(3).iter().any(|(tag, _)| tag == "#Tesla"));
+
+        let graph = get_graph(&posts, Some(&mars), 2);
+        let edges: Vec<_> = graph
+            .edges
+            .iter()
+            .map(|e| (e.source.as_str(), e.target.as_str(), e.weight))
+            .collect();
+        assert_eq!(edges.len(), 2);
+        assert!(edges.iter().all(|&(_, _, weight)| weight == 2));
+        let starship = graph
+            .nodes
+            .iter()
+            .find(|n| n.hashtag == "#Starship")
+            .unwrap();
+        assert_eq!(
+            (starship.id.as_str(), starship.mentions),
+            ("tag-starship", 4)
+        );
+    }
+
+    #[test]
+    fn breaks_trends_down_by_platform() {
+        let mut posts: Vec<SocialPost> = (0..12)
+            .map(|i| post(&format!("rd-{i}"), "Reddit", i * 5, "Great launch #Rust"))
+            .collect();
+        posts.extend((0..4).map(|i| post(&format!("x-{i}"), "X", i * 5, "Meh #rust #Mars")));
+
+        let trends = get_trends(&posts, TrendSort::Mentions, None);
+        // #Mars has fewer than MIN_TREND_MENTIONS posts.
+        assert_eq!(trends.len(), 1);
+        let rust = &trends[0];
+        assert_eq!((rust.id.as_str(), rust.mention_count), ("tag-rust", 16));
+        assert_eq!(rust.platform, "Reddit");
+        let platforms: Vec<_> = rust
+            .platforms
+            .iter()
+            .map(|p| (p.platform.as_str(), p.mention_count, p.share))
+            .collect();
+        assert_eq!(platforms, vec![("Reddit", 12, 75.0), ("X", 4, 25.0)]);
+        assert!(get_trends(&posts, TrendSort::Mentions, Some("x")).is_empty());
+
+        let mars = resolve_topic(&posts, "#mars").unwrap();
+        let summary = get_platforms(&posts, Some(&mars));
+        assert_eq!(summary.topic.as_deref(), Some("tag-mars"));
+        assert_eq!(summary.total_mentions, 4);
+        let x = &summary.platforms[0];
+        assert_eq!((x.platform.as_str(), x.share), ("X", 100.0));
+        assert_eq!((x.engagement.likes, x.engagement_per_post), (40, 13.0));
+
+        let everything = get_platforms(&posts, None);
+        assert_eq!((everything.topic, everything.total_mentions), (None, 16));
     }
 }
@@ -1,13 +1,26 @@
-use chrono::Utc;
-use rand::Rng;
-use serde::Serialize;
+use chrono::{DateTime, Datelike, DurationRound, NaiveDate, TimeDelta, Timelike, Utc};
+use chrono_tz::Tz;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use super::cities::City;
+use crate::analysis::fnv1a;
 
 #[derive(Debug, Clone, Serialize)]
 pub struct WeatherData {
     pub city: String,
     pub country: String,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub timezone: String,
+    /// The current time in the city, with its UTC offset.
+    pub local_time: String,
     pub current: CurrentWeather,
+    /// The next five days, starting tomorrow in the city's time zone.
     pub forecast: Vec<ForecastDay>,
+    /// The next 48 hours, starting with the next full hour.
+    pub hourly: Vec<HourlyForecast>,
     pub last_updated: String,
 }
 
@@ -31,74 +44,901 @@ pub struct ForecastDay {
     pub low_c: f64,
     pub condition: String,
     pub precipitation_chance: u32,
+    /// Expected total over the day.
+    pub precipitation_mm: f64,
     pub humidity: u32,
     pub wind_speed_kmh: f64,
 }
 
-/// Return weather data for the given city (defaults to Istanbul).
-pub fn get_weather(city: &str) -> WeatherData {
-    let mut rng = rand::thread_rng();
-    let now = Utc::now();
-
-    let (city_name, country, base_temp, base_humidity, condition) = match city.to_lowercase().as_str()
-    {
-        "london" => ("London", "United Kingdom", 8.0, 75u32, "Overcast"),
-        "new york" | "newyork" => ("New York", "United States", 2.0, 55, "Partly Cloudy"),
-        "tokyo" => ("Tokyo", "Japan", 10.0, 50, "Clear"),
-        "paris" => ("Paris", "France", 7.0, 70, "Light Rain"),
-        "berlin" => ("Berlin", "Germany", 3.0, 65, "Cloudy"),
-        "sydney" => ("Sydney", "Australia", 26.0, 60, "Sunny"),
-        "dubai" => ("Dubai", "UAE", 24.0, 40, "Sunny"),
-        "moscow" => ("Moscow", "Russia", -5.0, 80, "Snow"),
-        "mumbai" => ("Mumbai", "India", 30.0, 65, "Haze"),
-        "ankara" => ("Ankara", "Turkey", 2.0, 60, "Partly Cloudy"),
-        _ => ("Istanbul", "Turkey", 9.0, 68, "Partly Cloudy"),
-    };
+/// One hour of the forecast; daily entries summarize these.
+#[derive(Debug, Clone, Serialize)]
+pub struct HourlyForecast {
+    /// Start of the hour in the city's time zone.
+    pub time: String,
+    pub temperature_c: f64,
+    pub feels_like_c: f64,
+    pub dew_point_c: f64,
+    pub humidity: u32,
+    /// Percentage of the sky covered.
+    pub cloud_cover: u32,
+    pub precipitation_chance: u32,
+    /// Expected amount over the hour.
+    pub precipitation_mm: f64,
+    pub wind_speed_kmh: f64,
+    pub uv_index: u32,
+    pub condition: String,
+}
 
-    let temp_var: f64 = rng.gen_range(-2.0..2.0);
-    let temperature = (base_temp + temp_var).round();
-    let feels_like = (temperature - rng.gen_range(1.0..4.0)).round();
-    let wind_speed: f64 = (rng.gen_range(5.0_f64..30.0) * 10.0).round() / 10.0;
+/// One local day as recorded by the weather collector.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Observation {
+    pub date: NaiveDate,
+    pub high_c: f64,
+    pub low_c: f64,
+    /// Average of the hourly readings.
+    pub mean_c: f64,
+    pub precipitation_mm: f64,
+    pub humidity: u32,
+    pub wind_speed_kmh: f64,
+    pub condition: String,
+    /// Hours summarized; fewer than the day has while it is in progress.
+    #[serde(default)]
+    pub hours: u32,
+}
 
-    let wind_directions = ["N", "NE", "E", "SE", "S", "SW", "W", "NW"];
-    let wind_dir = wind_directions[rng.gen_range(0..8)];
+/// Long-term averages for one calendar month.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct MonthlyNormal {
+    /// 1 for January through 12 for December.
+    pub month: u32,
+    pub mean_c: f64,
+    pub high_c: f64,
+    pub low_c: f64,
+    pub humidity: u32,
+}
 
-    let conditions = ["Sunny", "Partly Cloudy", "Cloudy", "Overcast", "Light Rain", "Rain", "Clear"];
-
-    let forecast: Vec<ForecastDay> = (1..=5)
-        .map(|i| {
-            let date = now + chrono::Duration::days(i);
-            let day_var: f64 = rng.gen_range(-3.0..3.0);
-            let high = (base_temp + day_var + rng.gen_range(2.0..6.0)).round();
-            let low = (base_temp + day_var - rng.gen_range(2.0..5.0)).round();
-
-            ForecastDay {
-                date: date.format("%Y-%m-%d").to_string(),
-                high_c: high,
-                low_c: low,
-                condition: conditions[rng.gen_range(0..conditions.len())].to_string(),
-                precipitation_chance: rng.gen_range(0..80),
-                humidity: (base_humidity as i32 + rng.gen_range(-15..15)).clamp(20, 95) as u32,
-                wind_speed_kmh: (rng.gen_range(3.0_f64..35.0) * 10.0).round() / 10.0,
-            }
+/// How far an observed day was from its month's normal; positive means
+/// warmer or more humid.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct Deviation {
+    pub mean_c: f64,
+    pub high_c: f64,
+    pub low_c: f64,
+    pub humidity: i32,
+}
+
+impl Observation {
+    pub fn deviation(&self, normal: &MonthlyNormal) -> Deviation {
+        Deviation {
+            mean_c: round1(self.mean_c - normal.mean_c),
+            high_c: round1(self.high_c - normal.high_c),
+            low_c: round1(self.low_c - normal.low_c),
+            humidity: self.humidity as i32 - normal.humidity as i32,
+        }
+    }
+}
+
+/// Hours in the hourly forecast.
+const HOURLY_HOURS: usize = 48;
+
+/// Days in the daily forecast, starting tomorrow.
+const FORECAST_DAYS: i64 = 5;
+
+/// Local hours at which the diurnal temperature curve bottoms out and peaks.
+const COOLEST_HOUR: f64 = 6.0;
+const WARMEST_HOUR: f64 = 15.0;
+
+/// Return the weather for a city from the registry right now.
+pub fn get_weather(city: &City) -> WeatherData {
+    get_weather_at(city, Utc::now())
+}
+
+/// Return the weather for a city as of `now`.
+///
+/// Each local day gets an outlook drawn around the seasonal baseline for the
+/// city's latitude; hours follow from it, and the current conditions and
+/// daily summaries follow from the hours, so every figure agrees with the
+/// others. Days and hours are seeded from the city and their date, so the
+/// same hour reads the same on every call and in the recorded history.
+pub fn get_weather_at(city: &City, now: DateTime<Utc>) -> WeatherData {
+    let tz = city.tz();
+    let local_now = now.with_timezone(&tz);
+    let today = local_now.date_naive();
+
+    // One outlook per local day, from yesterday (for the small hours of
+    // today) to the day after the forecast ends (for its last evening).
+    let outlooks: Vec<DayOutlook> = (-1..=FORECAST_DAYS + 1)
+        .map(|offset| outlook(city, today + TimeDelta::days(offset)))
+        .collect();
+
+    // Every hour from the next one to the end of the last forecast day.
+    let this_hour = now.duration_trunc(TimeDelta::hours(1)).unwrap_or(now);
+    let hours: Vec<(DateTime<Tz>, HourlyForecast)> = (1..)
+        .map(|i| (this_hour + TimeDelta::hours(i)).with_timezone(&tz))
+        .take_while(|time| time.date_naive() <= today + TimeDelta::days(FORECAST_DAYS))
+        .map(|time| (time, hourly(city, &outlooks, &time)))
+        .collect();
+
+    let forecast: Vec<ForecastDay> = (1..=FORECAST_DAYS)
+        .map(|offset| {
+            let date = today + TimeDelta::days(offset);
+            let day: Vec<&HourlyForecast> = hours
+                .iter()
+                .filter(|(time, _)| time.date_naive() == date)
+                .map(|(_, hour)| hour)
+                .collect();
+            daily(date, &day)
         })
         .collect();
 
+    // Current conditions are those of the hour in progress.
+    let this_hour = this_hour.with_timezone(&tz);
+    let mut rng = StdRng::seed_from_u64(seed_for(city, &format!("{}:now", this_hour.to_rfc3339())));
+    let wind_directions = ["N", "NE", "E", "SE", "S", "SW", "W", "NW"];
+    let wind_dir = wind_directions[rng.gen_range(0..8)];
+    let now_hour = hourly(city, &outlooks, &this_hour);
+    // Rain and near-saturated air both cut visibility; fog below ~97%.
+    let visibility = if now_hour.humidity >= 97 {
+        rng.gen_range(0.2..1.0)
+    } else if now_hour.precipitation_mm > 0.0 {
+        (10.0 - 2.0 * now_hour.precipitation_mm).max(2.0)
+    } else {
+        20.0 - (now_hour.humidity as f64 - 40.0).max(0.0) / 5.0
+    };
+    // Lower pressure comes with the unsettled weather that brings rain.
+    let pressure = 1022.0 - 0.2 * now_hour.precipitation_chance as f64 + rng.gen_range(-3.0..3.0);
+
     WeatherData {
-        city: city_name.to_string(),
-        country: country.to_string(),
+        city: city.name.clone(),
+        country: city.country.clone(),
+        latitude: city.latitude,
+        longitude: city.longitude,
+        timezone: city.timezone.clone(),
+        local_time: local_now.to_rfc3339(),
         current: CurrentWeather {
-            temperature_c: temperature,
-            feels_like_c: feels_like,
-            humidity: (base_humidity as i32 + rng.gen_range(-10..10)).clamp(20, 95) as u32,
-            wind_speed_kmh: wind_speed,
+            temperature_c: now_hour.temperature_c,
+            feels_like_c: now_hour.feels_like_c,
+            humidity: now_hour.humidity,
+            wind_speed_kmh: now_hour.wind_speed_kmh,
             wind_direction: wind_dir.to_string(),
-            condition: condition.to_string(),
-            uv_index: rng.gen_range(1..10),
-            visibility_km: (rng.gen_range(5.0_f64..20.0) * 10.0).round() / 10.0,
-            pressure_hpa: rng.gen_range(1005..1025),
+            condition: now_hour.condition,
+            uv_index: now_hour.uv_index,
+            visibility_km: round1(visibility),
+            pressure_hpa: pressure.round() as u32,
         },
         forecast,
+        hourly: hours
+            .into_iter()
+            .take(HOURLY_HOURS)
+            .map(|(_, hour)| hour)
+            .collect(),
         last_updated: now.to_rfc3339(),
     }
 }
+
+/// Summarize the hours of local `date` in `city` that have begun by `now`,
+/// the way the collector records a day. The hours are the ones
+/// `get_weather` serves, so a recorded day matches what was reported for it.
+/// `None` if none of the day has begun yet.
+pub fn observe(city: &City, date: NaiveDate, now: DateTime<Utc>) -> Option<Observation> {
+    let tz = city.tz();
+    let outlooks: Vec<DayOutlook> = (-1..=1)
+        .map(|offset| outlook(city, date + TimeDelta::days(offset)))
+        .collect();
+    // Every UTC hour that falls on `date` locally; offsets reach ±14 hours.
+    let noon = date.and_hms_opt(12, 0, 0).unwrap_or_default().and_utc();
+    let hours: Vec<HourlyForecast> = (-38..38)
+        .map(|i| (noon + TimeDelta::hours(i)).with_timezone(&tz))
+        .filter(|time| time.date_naive() == date && *time <= now)
+        .map(|time| hourly(city, &outlooks, &time))
+        .collect();
+    if hours.is_empty() {
+        return None;
+    }
+    let mean = hours.iter().map(|h| h.temperature_c).sum::<f64>() / hours.len() as f64;
+    let day = daily(date, &hours.iter().collect::<Vec<_>>());
+
+    Some(Observation {
+        date,
+        high_c: day.high_c,
+        low_c: day.low_c,
+        mean_c: round1(mean),
+        precipitation_mm: day.precipitation_mm,
+        humidity: day.humidity,
+        wind_speed_kmh: day.wind_speed_kmh,
+        condition: day.condition,
+        hours: hours.len() as u32,
+    })
+}
+
+/// Non-leap year whose calendar the monthly normals are averaged over.
+const NORMALS_YEAR: i32 = 2001;
+
+/// Climatological normals for each month: the seasonal baseline for the
+/// city's latitude averaged over the month's days.
+pub fn normals(city: &City) -> Vec<MonthlyNormal> {
+    (1..=12)
+        .map(|month| {
+            let days: Vec<Baseline> = (1..=31)
+                .filter_map(|day| NaiveDate::from_ymd_opt(NORMALS_YEAR, month, day))
+                .map(|date| baseline(city.latitude, date))
+                .collect();
+            let average = |value: fn(&Baseline) -> f64| {
+                days.iter().map(value).sum::<f64>() / days.len() as f64
+            };
+            let mean = average(|b| b.mean_c);
+            let range = average(|b| b.range_c);
+            MonthlyNormal {
+                month,
+                mean_c: round1(mean),
+                high_c: round1(mean + range / 2.0),
+                low_c: round1(mean - range / 2.0),
+                humidity: average(|b| b.humidity).round() as u32,
+            }
+        })
+        .collect()
+}
+
+/// Typical weather for a latitude on a date.
+#[derive(Debug, Clone, Copy)]
+struct Baseline {
+    mean_c: f64,
+    /// Typical difference between the day's high and low.
+    range_c: f64,
+    humidity: f64,
+}
+
+/// Day of the year on which northern-hemisphere temperatures peak (~20 July).
+const WARMEST_DAY: f64 = 201.0;
+
+/// Seasonal baseline from latitude and date alone. Annual mean temperature
+/// falls off with the square of latitude and the seasonal swing grows with
+/// it, peaking in late July north of the equator and late January south of
+/// it. The dry subtropical belt around 25° gets lower humidity and, with
+/// clearer skies, a wider daily range. Coasts, altitude and monsoons are
+/// ignored, so this is a plausible climate rather than a city's real one.
+fn baseline(latitude: f64, date: NaiveDate) -> Baseline {
+    let annual_mean = 28.0 - 0.0065 * latitude * latitude;
+    let swing = 1.0 + 12.0 * latitude.abs().to_radians().sin();
+    let season =
+        (2.0 * std::f64::consts::PI * (date.ordinal() as f64 - WARMEST_DAY) / 365.25).cos();
+    let humidity = 72.0 - 28.0 * (-((latitude.abs() - 25.0) / 7.0).powi(2)).exp();
+    Baseline {
+        mean_c: annual_mean + swing * season * latitude.signum(),
+        range_c: 4.0 + (80.0 - humidity).max(0.0) * 0.2,
+        humidity,
+    }
+}
+
+/// The expected shape of one local day, from which its hours are drawn.
+#[derive(Debug, Clone)]
+struct DayOutlook {
+    date: NaiveDate,
+    high_c: f64,
+    low_c: f64,
+    /// Relative humidity at the day's mean temperature.
+    humidity: f64,
+    precipitation_chance: u32,
+    /// How hard it rains when it does.
+    rain_rate_mm: f64,
+    wind_speed_kmh: f64,
+}
+
+/// Share of days with a gale rather than an ordinary breeze.
+const STORM_CHANCE: f64 = 0.06;
+
+/// Draw a day's weather in `city` around its seasonal baseline. Humid days
+/// are more likely to be wet, and wet, cloudy days vary less between day and
+/// night.
+fn outlook(city: &City, date: NaiveDate) -> DayOutlook {
+    let mut rng = StdRng::seed_from_u64(seed_for(city, &date.format("%Y-%m-%d").to_string()));
+    let base = baseline(city.latitude, date);
+    let humidity = (base.humidity + rng.gen_range(-12.0..12.0)).clamp(15.0, 95.0);
+    let chance = ((humidity - 35.0) * 1.4 + rng.gen_range(-20.0..20.0)).clamp(0.0, 95.0);
+    let mean = base.mean_c + rng.gen_range(-3.0..3.0);
+    let range = base.range_c * rng.gen_range(0.8..1.2) * (1.0 - 0.4 * chance / 100.0);
+    DayOutlook {
+        date,
+        high_c: round1(mean + range / 2.0),
+        low_c: round1(mean - range / 2.0),
+        humidity,
+        precipitation_chance: chance.round() as u32,
+        rain_rate_mm: rng.gen_range(0.3..2.0),
+        wind_speed_kmh: if rng.gen_bool(STORM_CHANCE) {
+            rng.gen_range(45.0..85.0)
+        } else {
+            rng.gen_range(3.0..35.0)
+        },
+    }
+}
+
+/// The hour starting at `time`, with its own variation seeded from it.
+fn hourly(city: &City, outlooks: &[DayOutlook], time: &DateTime<Tz>) -> HourlyForecast {
+    let mut rng = StdRng::seed_from_u64(seed_for(city, &time.to_rfc3339()));
+    let date = time.date_naive();
+    let index = outlooks
+        .iter()
+        .position(|o| o.date == date)
+        .unwrap_or(1)
+        .clamp(1, outlooks.len() - 2);
+    let (previous, day, next) = (&outlooks[index - 1], &outlooks[index], &outlooks[index + 1]);
+
+    let local_hour = time.hour() as f64 + time.minute() as f64 / 60.0;
+    let temperature = diurnal(previous, day, next, local_hour);
+    // Moisture changes slowly, so hold the day's dew point and let relative
+    // humidity rise as the air cools overnight.
+    let dew_point = dew_point((day.high_c + day.low_c) / 2.0, day.humidity).min(temperature);
+    let humidity = relative_humidity(temperature, dew_point);
+    let chance = (day.precipitation_chance as i32 + rng.gen_range(-10..=10)).clamp(0, 100) as u32;
+    let cloud_cover = (chance as i32 + 15 + rng.gen_range(-15..=15)).clamp(0, 100) as u32;
+    let precipitation = if chance >= 30 {
+        round1(chance as f64 / 100.0 * day.rain_rate_mm)
+    } else {
+        0.0
+    };
+    let wind = (day.wind_speed_kmh + rng.gen_range(-3.0..3.0)).max(0.0);
+    let sun = solar_elevation(city.latitude, city.longitude, time.with_timezone(&Utc));
+
+    HourlyForecast {
+        time: time.to_rfc3339(),
+        temperature_c: round1(temperature),
+        feels_like_c: round1(feels_like(temperature, humidity, wind)),
+        dew_point_c: round1(dew_point),
+        humidity: humidity.round() as u32,
+        cloud_cover,
+        precipitation_chance: chance,
+        precipitation_mm: precipitation,
+        wind_speed_kmh: round1(wind),
+        uv_index: uv_index(sun, cloud_cover as f64),
+        condition: condition(cloud_cover as f64, precipitation, temperature, sun > 0.0).to_string(),
+    }
+}
+
+/// Summarize a local day's hours.
+fn daily(date: NaiveDate, hours: &[&HourlyForecast]) -> ForecastDay {
+    let count = hours.len().max(1) as f64;
+    let high = hours
+        .iter()
+        .map(|h| h.temperature_c)
+        .fold(f64::MIN, f64::max);
+    let low = hours
+        .iter()
+        .map(|h| h.temperature_c)
+        .fold(f64::MAX, f64::min);
+    let precipitation: f64 = hours.iter().map(|h| h.precipitation_mm).sum();
+    let cloud_cover = hours.iter().map(|h| h.cloud_cover as f64).sum::<f64>() / count;
+
+    ForecastDay {
+        date: date.format("%Y-%m-%d").to_string(),
+        high_c: high.round(),
+        low_c: low.round(),
+        condition: condition(cloud_cover, precipitation / 4.0, (high + low) / 2.0, true)
+            .to_string(),
+        precipitation_chance: hours
+            .iter()
+            .map(|h| h.precipitation_chance)
+            .max()
+            .unwrap_or(0),
+        precipitation_mm: round1(precipitation),
+        humidity: (hours.iter().map(|h| h.humidity as f64).sum::<f64>() / count).round() as u32,
+        wind_speed_kmh: round1(hours.iter().map(|h| h.wind_speed_kmh).sum::<f64>() / count),
+    }
+}
+
+/// Temperature at `hour` (local, fractional) on `day`: a half-cosine rise
+/// from the low at `COOLEST_HOUR` to the high at `WARMEST_HOUR`, then a
+/// slower fall towards the next morning's low.
+fn diurnal(previous: &DayOutlook, day: &DayOutlook, next: &DayOutlook, hour: f64) -> f64 {
+    let ease = |from: f64, to: f64, progress: f64| {
+        from + (to - from) * (1.0 - (std::f64::consts::PI * progress).cos()) / 2.0
+    };
+    let night = 24.0 - WARMEST_HOUR + COOLEST_HOUR;
+    if hour < COOLEST_HOUR {
+        ease(
+            previous.high_c,
+            day.low_c,
+            (hour + 24.0 - WARMEST_HOUR) / night,
+        )
+    } else if hour < WARMEST_HOUR {
+        ease(
+            day.low_c,
+            day.high_c,
+            (hour - COOLEST_HOUR) / (WARMEST_HOUR - COOLEST_HOUR),
+        )
+    } else {
+        ease(day.high_c, next.low_c, (hour - WARMEST_HOUR) / night)
+    }
+}
+
+/// Magnus formula coefficients (Alduchov & Eskridge, 1996).
+const MAGNUS_A: f64 = 17.625;
+const MAGNUS_B: f64 = 243.04;
+
+/// Dew point in °C for a temperature and relative humidity (percent).
+fn dew_point(temperature_c: f64, humidity: f64) -> f64 {
+    let gamma =
+        (humidity.max(1.0) / 100.0).ln() + MAGNUS_A * temperature_c / (MAGNUS_B + temperature_c);
+    MAGNUS_B * gamma / (MAGNUS_A - gamma)
+}
+
+/// Relative humidity (percent) of air at `temperature_c` with the given dew point.
+fn relative_humidity(temperature_c: f64, dew_point_c: f64) -> f64 {
+    let ratio = (MAGNUS_A * dew_point_c / (MAGNUS_B + dew_point_c)
+        - MAGNUS_A * temperature_c / (MAGNUS_B + temperature_c))
+        .exp();
+    (100.0 * ratio).clamp(1.0, 100.0)
+}
+
+/// Apparent temperature: wind chill in cold wind, heat index in humid heat,
+/// otherwise the air temperature.
+fn feels_like(temperature_c: f64, humidity: f64, wind_kmh: f64) -> f64 {
+    if temperature_c <= 10.0 && wind_kmh > 4.8 {
+        wind_chill(temperature_c, wind_kmh)
+    } else if temperature_c >= 26.7 {
+        heat_index(temperature_c, humidity).max(temperature_c)
+    } else {
+        temperature_c
+    }
+}
+
+/// Wind chill index used in Canada and the US (2001), valid at or below
+/// 10 °C with wind above 4.8 km/h.
+fn wind_chill(temperature_c: f64, wind_kmh: f64) -> f64 {
+    let v = wind_kmh.powf(0.16);
+    13.12 + 0.6215 * temperature_c - 11.37 * v + 0.3965 * temperature_c * v
+}
+
+/// US National Weather Service heat index: Steadman's simple estimate,
+/// refined with the Rothfusz regression once that reaches 80 °F.
+fn heat_index(temperature_c: f64, humidity: f64) -> f64 {
+    let t = temperature_c * 9.0 / 5.0 + 32.0;
+    let rh = humidity;
+    let simple = 0.5 * (t + 61.0 + (t - 68.0) * 1.2 + rh * 0.094);
+    let index = if simple < 80.0 {
+        simple
+    } else {
+        -42.379 + 2.049_015_23 * t + 10.143_331_27 * rh
+            - 0.224_755_41 * t * rh
+            - 0.006_837_83 * t * t
+            - 0.054_817_17 * rh * rh
+            + 0.001_228_74 * t * t * rh
+            + 0.000_852_82 * t * rh * rh
+            - 0.000_001_99 * t * t * rh * rh
+    };
+    (index - 32.0) * 5.0 / 9.0
+}
+
+/// Angle of the sun above the horizon in degrees, from the solar
+/// declination and hour angle (ignoring the equation of time).
+fn solar_elevation(latitude: f64, longitude: f64, time: DateTime<Utc>) -> f64 {
+    let day = time.ordinal() as f64;
+    let declination =
+        (23.44_f64.to_radians()) * (2.0 * std::f64::consts::PI * (284.0 + day) / 365.0).sin();
+    let solar_hour = time.hour() as f64 + time.minute() as f64 / 60.0 + longitude / 15.0;
+    let hour_angle = (15.0 * (solar_hour - 12.0)).to_radians();
+    let latitude = latitude.to_radians();
+    (latitude.sin() * declination.sin() + latitude.cos() * declination.cos() * hour_angle.cos())
+        .asin()
+        .to_degrees()
+}
+
+/// UV index from the sun's elevation, about 12 with the sun overhead in a
+/// clear sky; thick cloud blocks up to roughly two thirds of it.
+fn uv_index(solar_elevation: f64, cloud_cover: f64) -> u32 {
+    if solar_elevation <= 0.0 {
+        return 0;
+    }
+    let clear_sky = 12.5 * solar_elevation.to_radians().sin().powf(2.4);
+    let cloud = 1.0 - 0.65 * (cloud_cover / 100.0).powi(2);
+    (clear_sky * cloud).round() as u32
+}
+
+/// Describe the sky from cloud cover (percent) and rain over an hour.
+fn condition(
+    cloud_cover: f64,
+    precipitation_mm: f64,
+    temperature_c: f64,
+    daylight: bool,
+) -> &'static str {
+    if precipitation_mm >= 0.2 && temperature_c <= 0.5 {
+        "Snow"
+    } else if precipitation_mm >= 2.5 {
+        "Rain"
+    } else if precipitation_mm >= 0.2 {
+        "Light Rain"
+    } else if cloud_cover >= 90.0 {
+        "Overcast"
+    } else if cloud_cover >= 65.0 {
+        "Cloudy"
+    } else if cloud_cover >= 25.0 {
+        "Partly Cloudy"
+    } else if daylight {
+        "Sunny"
+    } else {
+        "Clear"
+    }
+}
+
+/// Hash of the city ID and a date or time key, used to seed the model.
+fn seed_for(city: &City, key: &str) -> u64 {
+    fnv1a(format!("{}:{key}", city.id).as_bytes())
+}
+
+fn round1(value: f64) -> f64 {
+    (value * 10.0).round() / 10.0
+}
+
+/// Unit system for weather responses. Data is generated in metric units;
+/// other systems are converted on the way out.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Units {
+    /// °C, km/h, km, hPa, mm.
+    #[default]
+    Metric,
+    /// °F, mph, mi, inHg, in.
+    Imperial,
+    /// K, m/s, m, Pa, mm.
+    Si,
+}
+
+/// Metric field suffixes and the quantity each one measures.
+const METRIC_SUFFIXES: &[(&str, Quantity)] = &[
+    ("_c", Quantity::Temperature),
+    ("_kmh", Quantity::Speed),
+    ("_km", Quantity::Distance),
+    ("_hpa", Quantity::Pressure),
+    ("_mm", Quantity::Precipitation),
+];
+
+#[derive(Debug, Clone, Copy)]
+enum Quantity {
+    Temperature,
+    Speed,
+    Distance,
+    Pressure,
+    Precipitation,
+}
+
+impl Units {
+    /// Field suffix, unit symbol and conversion from the metric value, with
+    /// the number of decimals kept.
+    fn unit(self, quantity: Quantity) -> (&'static str, &'static str, fn(f64) -> f64, i32) {
+        match (self, quantity) {
+            (Self::Metric, Quantity::Temperature) => ("_c", "°C", |c| c, 1),
+            (Self::Metric, Quantity::Speed) => ("_kmh", "km/h", |kmh| kmh, 1),
+            (Self::Metric, Quantity::Distance) => ("_km", "km", |km| km, 1),
+            (Self::Metric, Quantity::Pressure) => ("_hpa", "hPa", |hpa| hpa, 0),
+            (Self::Metric | Self::Si, Quantity::Precipitation) => ("_mm", "mm", |mm| mm, 1),
+            (Self::Imperial, Quantity::Temperature) => ("_f", "°F", |c| c * 9.0 / 5.0 + 32.0, 1),
+            (Self::Imperial, Quantity::Speed) => ("_mph", "mph", |kmh| kmh / 1.609_344, 1),
+            (Self::Imperial, Quantity::Distance) => ("_mi", "mi", |km| km / 1.609_344, 1),
+            (Self::Imperial, Quantity::Pressure) => ("_inhg", "inHg", |hpa| hpa / 33.863_886, 2),
+            (Self::Imperial, Quantity::Precipitation) => ("_in", "in", |mm| mm / 25.4, 2),
+            (Self::Si, Quantity::Temperature) => ("_k", "K", |c| c + 273.15, 2),
+            (Self::Si, Quantity::Speed) => ("_ms", "m/s", |kmh| kmh / 3.6, 1),
+            (Self::Si, Quantity::Distance) => ("_m", "m", |km| km * 1000.0, 0),
+            (Self::Si, Quantity::Pressure) => ("_pa", "Pa", |hpa| hpa * 100.0, 0),
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Metric => "metric",
+            Self::Imperial => "imperial",
+            Self::Si => "si",
+        }
+    }
+
+    /// The symbol used for each quantity, for clients labelling values.
+    pub fn metadata(self) -> serde_json::Value {
+        serde_json::json!({
+            "system": self.name(),
+            "temperature": self.unit(Quantity::Temperature).1,
+            "speed": self.unit(Quantity::Speed).1,
+            "distance": self.unit(Quantity::Distance).1,
+            "pressure": self.unit(Quantity::Pressure).1,
+            "precipitation": self.unit(Quantity::Precipitation).1,
+        })
+    }
+}
+
+/// Serialize `data` in `units`, renaming every metric field to the unit it
+/// now holds (`temperature_c` becomes `temperature_f`, `wind_speed_kmh`
+/// becomes `wind_speed_mph`) and adding a `units` object describing them.
+pub fn with_units<T: Serialize>(data: &T, units: Units) -> serde_json::Value {
+    let mut value = serde_json::json!(data);
+    if units != Units::Metric {
+        convert(&mut value, units);
+    }
+    if let serde_json::Value::Object(fields) = &mut value {
+        fields.insert("units".to_string(), units.metadata());
+    }
+    value
+}
+
+fn convert(value: &mut serde_json::Value, units: Units) {
+    match value {
+        serde_json::Value::Object(fields) => {
+            let converted: serde_json::Map<String, serde_json::Value> = std::mem::take(fields)
+                .into_iter()
+                .map(|(key, mut field)| {
+                    let metric = METRIC_SUFFIXES
+                        .iter()
+                        .find(|(suffix, _)| key.ends_with(suffix));
+                    match (metric, field.as_f64()) {
+                        (Some(&(suffix, quantity)), Some(number)) => {
+                            let (new_suffix, _, to_unit, decimals) = units.unit(quantity);
+                            let scale = 10f64.powi(decimals);
+                            let key = format!("{}{new_suffix}", &key[..key.len() - suffix.len()]);
+                            (
+                                key,
+                                serde_json::json!((to_unit(number) * scale).round() / scale),
+                            )
+                        }
+                        _ => {
+                            convert(&mut field, units);
+                            (key, field)
+                        }
+                    }
+                })
+                .collect();
+            *fields = converted;
+        }
+        serde_json::Value::Array(items) => {
+            for item in items {
+                convert(item, units);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Reading {
+        city: &'static str,
+        temperature_c: f64,
+        wind_speed_kmh: f64,
+        pressure_hpa: u32,
+        hours: Vec<Hour>,
+    }
+
+    #[derive(Serialize)]
+    struct Hour {
+        dew_point_c: f64,
+        precipitation_mm: f64,
+        visibility_km: f64,
+    }
+
+    fn reading() -> Reading {
+        Reading {
+            city: "Istanbul",
+            temperature_c: 20.0,
+            wind_speed_kmh: 36.0,
+            pressure_hpa: 1013,
+            hours: vec![Hour {
+                dew_point_c: -40.0,
+                precipitation_mm: 25.4,
+                visibility_km: 10.0,
+            }],
+        }
+    }
+
+    #[test]
+    fn hourly_forecast_follows_local_days() {
+        let city = crate::mock_data::cities::lookup("sydney").unwrap();
+        let data = get_weather(city);
+        let tomorrow = Utc::now().with_timezone(&city.tz()).date_naive() + TimeDelta::days(1);
+        assert_eq!(data.forecast.len(), FORECAST_DAYS as usize);
+        assert_eq!(
+            data.forecast[0].date,
+            tomorrow.format("%Y-%m-%d").to_string()
+        );
+        assert_eq!(data.hourly.len(), HOURLY_HOURS);
+        for hour in &data.hourly {
+            assert!(hour.time.ends_with("+10:00") || hour.time.ends_with("+11:00"));
+            assert!(hour.dew_point_c <= hour.temperature_c);
+            let date = &hour.time[..10];
+            if let Some(day) = data.forecast.iter().find(|d| d.date == date) {
+                assert!(hour.temperature_c.round() <= day.high_c);
+                assert!(hour.temperature_c.round() >= day.low_c);
+            }
+        }
+    }
+
+    #[test]
+    fn readings_hold_for_the_hour() {
+        let city = crate::mock_data::cities::lookup("sydney").unwrap();
+        let at =
+            |now: &str| serde_json::to_value(get_weather_at(city, now.parse().unwrap())).unwrap();
+        let (early, late) = (at("2026-03-09T04:05:00Z"), at("2026-03-09T04:55:00Z"));
+        assert_eq!(early["current"], late["current"]);
+        assert_eq!(early["hourly"], late["hourly"]);
+        assert_eq!(early["forecast"], late["forecast"]);
+        // An hour on, the forecast has moved along rather than been redrawn.
+        let next = at("2026-03-09T05:05:00Z");
+        assert_eq!(next["hourly"][0], early["hourly"][1]);
+        assert_eq!(next["forecast"], early["forecast"]);
+    }
+
+    #[test]
+    fn diurnal_curve_peaks_mid_afternoon() {
+        let day = |low_c, high_c| DayOutlook {
+            date: NaiveDate::default(),
+            high_c,
+            low_c,
+            humidity: 60.0,
+            precipitation_chance: 0,
+            rain_rate_mm: 0.0,
+            wind_speed_kmh: 10.0,
+        };
+        let (previous, today, next) = (day(0.0, 10.0), day(2.0, 12.0), day(4.0, 14.0));
+        let at = |hour| diurnal(&previous, &today, &next, hour);
+        assert_eq!(at(COOLEST_HOUR), 2.0);
+        assert_eq!(at(WARMEST_HOUR), 12.0);
+        // Evenings cool from today's high towards tomorrow's low, small
+        // hours from yesterday's high towards today's.
+        assert!(at(23.0) < 12.0 && at(23.0) > 4.0);
+        assert!(at(1.0) < 10.0 && at(1.0) > 2.0);
+        assert!(at(10.0) > at(8.0) && at(18.0) < at(16.0));
+    }
+
+    #[test]
+    fn seasons_follow_latitude_and_hemisphere() {
+        let january = NaiveDate::from_ymd_opt(2026, 1, 15).unwrap();
+        let july = NaiveDate::from_ymd_opt(2026, 7, 15).unwrap();
+        let moscow = (baseline(55.8, january), baseline(55.8, july));
+        let sydney = (baseline(-33.9, january), baseline(-33.9, july));
+        let singapore = (baseline(1.4, january), baseline(1.4, july));
+        assert!(moscow.0.mean_c < -3.0 && moscow.1.mean_c > 15.0);
+        assert!(sydney.0.mean_c > sydney.1.mean_c + 10.0);
+        assert!((singapore.0.mean_c - singapore.1.mean_c).abs() < 3.0);
+        assert!(baseline(25.0, july).humidity < baseline(50.0, july).humidity);
+    }
+
+    #[test]
+    fn observations_cover_one_local_day_near_normal() {
+        let city = crate::mock_data::cities::lookup("moscow").unwrap();
+        let normals = normals(city);
+        assert_eq!(normals.len(), 12);
+        assert!(normals[0].mean_c < normals[6].mean_c - 15.0);
+        assert!(normals
+            .iter()
+            .all(|n| n.low_c < n.mean_c && n.mean_c < n.high_c));
+
+        let date = NaiveDate::from_ymd_opt(2026, 1, 15).unwrap();
+        let day = observe(city, date, Utc::now()).unwrap();
+        assert_eq!((day.date, day.hours), (date, 24));
+        assert!(day.low_c <= day.mean_c.round() && day.mean_c.round() <= day.high_c);
+        let deviation = day.deviation(&normals[0]);
+        assert!(deviation.mean_c.abs() < 6.0, "{deviation:?}");
+        assert_eq!(deviation.high_c, round1(day.high_c - normals[0].high_c));
+    }
+
+    #[test]
+    fn observations_match_what_was_served() {
+        let city = crate::mock_data::cities::lookup("tokyo").unwrap();
+        let served = get_weather_at(city, "2026-05-03T22:20:00Z".parse().unwrap());
+        // 07:20 on 4 May in Tokyo; recorded once 5 May has begun there.
+        let tomorrow = &served.forecast[0];
+        let date = NaiveDate::from_ymd_opt(2026, 5, 5).unwrap();
+        let day = observe(city, date, "2026-05-05T15:00:00Z".parse().unwrap()).unwrap();
+        assert_eq!(tomorrow.date, "2026-05-05");
+        assert_eq!(
+            (day.high_c, day.low_c, day.precipitation_mm, day.humidity),
+            (
+                tomorrow.high_c,
+                tomorrow.low_c,
+                tomorrow.precipitation_mm,
+                tomorrow.humidity
+            )
+        );
+        assert_eq!(day.condition, tomorrow.condition);
+
+        // Today so far: midnight to 07:00, ending on the current reading.
+        let today = NaiveDate::from_ymd_opt(2026, 5, 4).unwrap();
+        let so_far = observe(city, today, "2026-05-03T22:20:00Z".parse().unwrap()).unwrap();
+        assert_eq!(so_far.hours, 8);
+        assert!(so_far.low_c <= served.current.temperature_c.round());
+        assert!(served.current.temperature_c.round() <= so_far.high_c);
+        assert!(observe(
+            city,
+            today + TimeDelta::days(1),
+            "2026-05-03T22:20:00Z".parse().unwrap()
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn feels_like_uses_wind_chill_and_heat_index() {
+        assert!((wind_chill(-10.0, 30.0) - -19.5).abs() < 0.1);
+        assert!((heat_index(32.0, 70.0) - 40.6).abs() < 0.5);
+        assert_eq!(feels_like(18.0, 90.0, 40.0), 18.0);
+        assert!(feels_like(0.0, 50.0, 20.0) < 0.0);
+        assert!(feels_like(35.0, 60.0, 10.0) > 35.0);
+    }
+
+    #[test]
+    fn conditions_agree_with_precipitation() {
+        for city in crate::mock_data::cities::cities() {
+            let data = get_weather(city);
+            let current = &data.current;
+            assert!(current.uv_index <= 13, "{}", city.id);
+            for hour in &data.hourly {
+                let wet = matches!(hour.condition.as_str(), "Rain" | "Light Rain" | "Snow");
+                assert_eq!(wet, hour.precipitation_mm >= 0.2, "{} {:?}", city.id, hour);
+                if hour.precipitation_chance < 30 {
+                    assert!(!wet);
+                }
+                if hour.condition == "Sunny" {
+                    assert!(hour.uv_index > 0 || hour.cloud_cover < 25);
+                }
+            }
+            for day in &data.forecast {
+                if day.precipitation_chance >= 60 {
+                    assert_ne!(day.condition, "Sunny", "{} {:?}", city.id, day);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn uv_needs_the_sun() {
+        let noon = DateTime::parse_from_rfc3339("2026-06-21T12:00:00Z")
+            .unwrap()
+            .to_utc();
+        let midnight = DateTime::parse_from_rfc3339("2026-06-21T00:00:00Z")
+            .unwrap()
+            .to_utc();
+        let overhead = solar_elevation(23.44, 0.0, noon);
+        assert!((overhead - 90.0).abs() < 1.0);
+        assert!(solar_elevation(51.5, 0.0, midnight) < 0.0);
+        assert_eq!(uv_index(overhead, 0.0), 12);
+        assert!(uv_index(overhead, 100.0) < 6);
+        assert_eq!(uv_index(-5.0, 0.0), 0);
+    }
+
+    #[test]
+    fn dew_point_round_trips_humidity() {
+        let dew = dew_point(25.0, 60.0);
+        assert!((dew - 16.7).abs() < 0.1);
+        assert!((relative_humidity(25.0, dew) - 60.0).abs() < 1e-6);
+        assert_eq!(relative_humidity(10.0, 10.0), 100.0);
+    }
+
+    #[test]
+    fn metric_is_unchanged_apart_from_metadata() {
+        let value = with_units(&reading(), Units::Metric);
+        assert_eq!(value["temperature_c"], 20.0);
+        assert_eq!(value["pressure_hpa"], 1013);
+        assert_eq!(value["units"]["temperature"], "°C");
+    }
+
+    #[test]
+    fn converts_and_renames_imperial_fields() {
+        let value = with_units(&reading(), Units::Imperial);
+        assert_eq!(value["city"], "Istanbul");
+        assert_eq!(value["temperature_f"], 68.0);
+        assert_eq!(value["wind_speed_mph"], 22.4);
+        assert_eq!(value["pressure_inhg"], 29.91);
+        assert_eq!(value["hours"][0]["dew_point_f"], -40.0);
+        assert_eq!(value["hours"][0]["precipitation_in"], 1.0);
+        assert_eq!(value["hours"][0]["visibility_mi"], 6.2);
+        assert!(value.get("temperature_c").is_none());
+        assert_eq!(value["units"]["speed"], "mph");
+    }
+
+    #[test]
+    fn converts_si_fields() {
+        let value = with_units(&reading(), Units::Si);
+        assert_eq!(value["temperature_k"], 293.15);
+        assert_eq!(value["wind_speed_ms"], 10.0);
+        assert_eq!(value["pressure_pa"], 101_300.0);
+        assert_eq!(value["hours"][0]["visibility_m"], 10_000.0);
+        assert_eq!(value["hours"][0]["precipitation_mm"], 25.4);
+    }
+}
@@ -0,0 +1,94 @@
+use axum::extract::{ConnectInfo, State};
+use axum::http::header::AUTHORIZATION;
+use axum::http::{HeaderMap, StatusCode};
+use axum::Json;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use crate::state::AppState;
+use crate::storage::records;
+
+/// Disk used by each persisted series, with its retention policy, and what
+/// the latest background maintenance pass did. Requires the `ADMIN_TOKEN`
+/// as a bearer token.
+pub async fn get_storage(
+    State(state): State<AppState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    state
+        .rate_limiter
+        .check_rate_limit(&addr.ip().to_string(), "admin/storage")?;
+    authorize(state.admin.token.as_deref(), &headers)?;
+
+    let store = Arc::clone(&state.store);
+    let series = tokio::task::spawn_blocking(move || records::usage(&store))
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let last_maintenance = *state
+        .maintenance
+        .read()
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(Json(serde_json::json!({
+        "total_bytes": series.iter().map(|s| s.bytes).sum::<u64>(),
+        "series": series,
+        "last_maintenance": last_maintenance,
+    })))
+}
+
+/// Admin endpoints do not exist without a configured token (404), and need it
+/// as `Authorization: Bearer <token>` when one is set (401).
+pub(crate) fn authorize(token: Option<&str>, headers: &HeaderMap) -> Result<(), StatusCode> {
+    let token = token.ok_or(StatusCode::NOT_FOUND)?;
+    let given = headers
+        .get(AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .ok_or(StatusCode::UNAUTHORIZED)?;
+    // Compare every byte so the time taken does not reveal a matching prefix.
+    let matches = given.len() == token.len()
+        && given
+            .bytes()
+            .zip(token.bytes())
+            .fold(0, |diff, (a, b)| diff | (a ^ b))
+            == 0;
+    if matches {
+        Ok(())
+    } else {
+        Err(StatusCode::UNAUTHORIZED)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    fn bearer(value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(AUTHORIZATION, HeaderValue::from_str(value).unwrap());
+        headers
+    }
+
+    #[test]
+    fn requires_the_configured_admin_token() {
+        assert_eq!(
+            authorize(None, &bearer("Bearer secret")),
+            Err(StatusCode::NOT_FOUND)
+        );
+        assert_eq!(
+            authorize(Some("secret"), &HeaderMap::new()),
+            Err(StatusCode::UNAUTHORIZED)
+        );
+        assert_eq!(
+            authorize(Some("secret"), &bearer("Bearer secreT")),
+            Err(StatusCode::UNAUTHORIZED)
+        );
+        assert_eq!(
+            authorize(Some("secret"), &bearer("secret")),
+            Err(StatusCode::UNAUTHORIZED)
+        );
+        assert_eq!(authorize(Some("secret"), &bearer("Bearer secret")), Ok(()));
+    }
+}
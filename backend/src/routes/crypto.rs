@@ -1,10 +1,15 @@
-use axum::extract::{ConnectInfo, State};
+use axum::extract::{ConnectInfo, Path, Query, State};
 use axum::http::StatusCode;
 use axum::Json;
+use chrono::{Duration, TimeDelta, Utc};
+use serde::{Deserialize, Serialize};
 use std::net::SocketAddr;
+use std::sync::Arc;
 
+use super::parse_bound;
 use crate::mock_data::crypto;
 use crate::state::AppState;
+use crate::storage::records;
 
 pub async fn get_prices(
     State(state): State<AppState>,
@@ -21,3 +26,97 @@ pub async fn get_prices(
         "currency": "USD",
     })))
 }
+
+/// Bucket size for `/api/crypto/prices/{symbol}/history`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Interval {
+    /// Every recorded tick.
+    #[default]
+    Tick,
+    Minute,
+    Hour,
+    Day,
+}
+
+impl Interval {
+    fn bucket(self) -> Option<TimeDelta> {
+        match self {
+            Self::Tick => None,
+            Self::Minute => Some(TimeDelta::minutes(1)),
+            Self::Hour => Some(TimeDelta::hours(1)),
+            Self::Day => Some(TimeDelta::days(1)),
+        }
+    }
+}
+
+/// Query parameters for `/api/crypto/prices/{symbol}/history`.
+///
+/// `from` and `to` accept either `YYYY-MM-DD` or RFC 3339 timestamps.
+#[derive(Debug, Deserialize)]
+pub struct HistoryQuery {
+    pub from: Option<String>,
+    pub to: Option<String>,
+    #[serde(default)]
+    pub interval: Interval,
+}
+
+/// Recorded prices for a coin: raw ticks (kept for a week) or open, high,
+/// low and close bars per interval. Defaults to the last 24 hours, or 30 days
+/// of daily bars.
+pub async fn get_history(
+    State(state): State<AppState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    Path(symbol): Path<String>,
+    Query(query): Query<HistoryQuery>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    state
+        .rate_limiter
+        .check_rate_limit(&addr.ip().to_string(), "crypto/history")?;
+
+    let symbol = symbol.to_uppercase();
+    if !crypto::get_prices()
+        .iter()
+        .any(|coin| coin.symbol == symbol)
+    {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    let now = Utc::now();
+    let to = match query.to.as_deref() {
+        Some(raw) => parse_bound(raw, true)
+            .ok_or(StatusCode::BAD_REQUEST)?
+            .min(now),
+        None => now,
+    };
+    let from = match query.from.as_deref() {
+        Some(raw) => parse_bound(raw, false).ok_or(StatusCode::BAD_REQUEST)?,
+        None if query.interval == Interval::Day => to - Duration::days(30),
+        None => to - Duration::hours(24),
+    };
+    if from > to {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let store = Arc::clone(&state.store);
+    let key = symbol.clone();
+    let bucket = query.interval.bucket();
+    let (count, prices) = tokio::task::spawn_blocking(move || match bucket {
+        Some(bucket) => records::price_bars(&store, &key, from, to, bucket)
+            .map(|bars| (bars.len(), serde_json::json!(bars))),
+        None => store
+            .query(&records::PRICES, Some(&key), from, to)
+            .map(|ticks| (ticks.len(), serde_json::json!(ticks))),
+    })
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(serde_json::json!({
+        "symbol": symbol,
+        "from": from.to_rfc3339(),
+        "to": to.to_rfc3339(),
+        "interval": query.interval,
+        "data_points": count,
+        "prices": prices,
+    })))
+}
@@ -1,10 +1,15 @@
-use axum::extract::{ConnectInfo, Path, State};
+use axum::extract::{ConnectInfo, Path, Query, State};
 use axum::http::StatusCode;
 use axum::Json;
+use chrono::{DateTime, Duration, DurationRound, Utc};
+use serde::Deserialize;
 use std::net::SocketAddr;
+use std::sync::Arc;
 
-use crate::mock_data::ecommerce;
+use super::parse_bound;
+use crate::mock_data::ecommerce::{self, DealCriteria, Granularity};
 use crate::state::AppState;
+use crate::storage::records;
 
 pub async fn get_products(
     State(state): State<AppState>,
@@ -21,19 +26,194 @@ pub async fn get_products(
     })))
 }
 
+pub async fn get_product(
+    State(state): State<AppState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    Path(product_id): Path<String>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    state
+        .rate_limiter
+        .check_rate_limit(&addr.ip().to_string(), "ecommerce/product")?;
+
+    let product = ecommerce::get_product(&product_id).ok_or(StatusCode::NOT_FOUND)?;
+    Ok(Json(serde_json::json!(product)))
+}
+
+pub async fn get_offers(
+    State(state): State<AppState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    Path(product_id): Path<String>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    state
+        .rate_limiter
+        .check_rate_limit(&addr.ip().to_string(), "ecommerce/offers")?;
+
+    let offers = ecommerce::get_offers(&product_id).ok_or(StatusCode::NOT_FOUND)?;
+    let best_offer = ecommerce::best_offer(&offers);
+    Ok(Json(serde_json::json!({
+        "product_id": product_id,
+        "count": offers.len(),
+        "best_offer": best_offer,
+        "offers": offers,
+    })))
+}
+
+pub async fn get_deals(
+    State(state): State<AppState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    Query(criteria): Query<DealCriteria>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    state
+        .rate_limiter
+        .check_rate_limit(&addr.ip().to_string(), "ecommerce/deals")?;
+
+    if !criteria.is_valid() {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let deals = ecommerce::get_deals(&criteria);
+    Ok(Json(serde_json::json!({
+        "count": deals.len(),
+        "criteria": criteria,
+        "deals": deals,
+    })))
+}
+
+/// Query parameters for `/api/ecommerce/prices/{product_id}`.
+///
+/// `from` and `to` accept either `YYYY-MM-DD` or RFC 3339 timestamps.
+#[derive(Debug, Deserialize)]
+pub struct PriceQuery {
+    pub from: Option<String>,
+    pub to: Option<String>,
+    #[serde(default)]
+    pub granularity: Granularity,
+}
+
+/// Longest window, in days, that may be requested at hourly granularity.
+const MAX_HOURLY_WINDOW_DAYS: i64 = 31;
+
+/// The product's price series and summary over the window, along with the
+/// prices the price tracker recorded from saved pages in it.
 pub async fn get_prices(
     State(state): State<AppState>,
     ConnectInfo(addr): ConnectInfo<SocketAddr>,
     Path(product_id): Path<String>,
+    Query(query): Query<PriceQuery>,
 ) -> Result<Json<serde_json::Value>, StatusCode> {
     state
         .rate_limiter
         .check_rate_limit(&addr.ip().to_string(), "ecommerce/prices")?;
 
-    let history = ecommerce::get_price_trends(&product_id);
+    let (from, to) = price_window(&query, Utc::now())?;
+    let history = ecommerce::get_price_history(&product_id, from, to, query.granularity)
+        .ok_or(StatusCode::NOT_FOUND)?;
+    let summary = ecommerce::summarize(&history);
+    let store = Arc::clone(&state.store);
+    let key = product_id.clone();
+    let recorded = tokio::task::spawn_blocking(move || {
+        store.query(&records::PRODUCT_PRICES, Some(&key), from, to)
+    })
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
     Ok(Json(serde_json::json!({
         "product_id": product_id,
+        "from": from.to_rfc3339(),
+        "to": to.to_rfc3339(),
+        "granularity": query.granularity,
         "data_points": history.len(),
         "price_history": history,
+        "summary": summary,
+        "recorded": recorded,
     })))
 }
+
+/// Resolve the requested window, clamping `to` at `now`. Hourly windows may
+/// span at most `MAX_HOURLY_WINDOW_DAYS`.
+fn price_window(
+    query: &PriceQuery,
+    now: DateTime<Utc>,
+) -> Result<(DateTime<Utc>, DateTime<Utc>), StatusCode> {
+    let to = match query.to.as_deref() {
+        Some(raw) => parse_bound(raw, true)
+            .ok_or(StatusCode::BAD_REQUEST)?
+            .min(now),
+        None => now,
+    };
+    let from = match query.from.as_deref() {
+        Some(raw) => parse_bound(raw, false).ok_or(StatusCode::BAD_REQUEST)?,
+        None => default_from(to, query.granularity),
+    };
+    if from > to {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    if query.granularity == Granularity::Hour && to - from > Duration::days(MAX_HOURLY_WINDOW_DAYS)
+    {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    Ok((from, to))
+}
+
+/// Default window: 48 hours, 30 days or 12 weeks ending at `to`.
+fn default_from(to: DateTime<Utc>, granularity: Granularity) -> DateTime<Utc> {
+    match granularity {
+        Granularity::Hour => {
+            to.duration_trunc(Duration::hours(1)).unwrap_or(to) - Duration::hours(47)
+        }
+        Granularity::Day => start_of_day(to - Duration::days(29)),
+        Granularity::Week => start_of_day(to - Duration::weeks(11)),
+    }
+}
+
+fn start_of_day(ts: DateTime<Utc>) -> DateTime<Utc> {
+    ts.date_naive()
+        .and_hms_opt(0, 0, 0)
+        .map(|t| t.and_utc())
+        .unwrap_or(ts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query(from: Option<&str>, to: Option<&str>, granularity: Granularity) -> PriceQuery {
+        PriceQuery {
+            from: from.map(str::to_string),
+            to: to.map(str::to_string),
+            granularity,
+        }
+    }
+
+    #[test]
+    fn caps_hourly_windows_at_31_days() {
+        let now = "2026-03-31T12:00:00Z".parse().unwrap();
+        let hourly = |from| price_window(&query(Some(from), None, Granularity::Hour), now);
+        assert!(hourly("2026-03-01").is_ok());
+        assert_eq!(hourly("2026-02-27"), Err(StatusCode::BAD_REQUEST));
+        let daily = query(Some("2025-06-01"), None, Granularity::Day);
+        assert!(price_window(&daily, now).is_ok());
+    }
+
+    #[test]
+    fn defaults_and_clamps_the_window() {
+        let now: DateTime<Utc> = "2026-03-31T12:20:00Z".parse().unwrap();
+        let (from, to) = price_window(&query(None, None, Granularity::Hour), now).unwrap();
+        assert_eq!(
+            (from.to_rfc3339(), to),
+            ("2026-03-29T13:00:00+00:00".into(), now)
+        );
+        let hours = ecommerce::get_price_history("elec-001", from, to, Granularity::Hour).unwrap();
+        assert_eq!(hours.len(), 48);
+
+        let (from, to) =
+            price_window(&query(None, Some("2027-01-01"), Granularity::Day), now).unwrap();
+        assert_eq!(to, now);
+        assert_eq!(from.to_rfc3339(), "2026-03-02T00:00:00+00:00");
+
+        let reversed = query(Some("2026-03-10"), Some("2026-03-01"), Granularity::Day);
+        assert_eq!(price_window(&reversed, now), Err(StatusCode::BAD_REQUEST));
+        let garbage = query(Some("yesterday"), None, Granularity::Day);
+        assert_eq!(price_window(&garbage, now), Err(StatusCode::BAD_REQUEST));
+    }
+}
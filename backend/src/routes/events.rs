@@ -0,0 +1,41 @@
+use axum::extract::{ConnectInfo, State};
+use axum::http::StatusCode;
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::IntoResponse;
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use tokio::sync::broadcast::error::RecvError;
+use tokio_stream::wrappers::ReceiverStream;
+
+use crate::state::AppState;
+
+/// Stream every event published from now on, e.g. `weather.alert`, as SSE.
+pub async fn get_events(
+    State(state): State<AppState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+) -> Result<impl IntoResponse, StatusCode> {
+    state
+        .rate_limiter
+        .check_rate_limit(&addr.ip().to_string(), "events")?;
+
+    let (tx, rx) = tokio::sync::mpsc::channel::<Result<Event, Infallible>>(32);
+    let mut events = state.events.subscribe();
+
+    tokio::spawn(async move {
+        loop {
+            let event = match events.recv().await {
+                Ok(event) => event,
+                // A slow client misses the oldest events rather than the stream.
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => break,
+            };
+            let data = serde_json::to_string(&event).unwrap();
+            let sse = Event::default().event(&event.kind).id(&event.id).data(data);
+            if tx.send(Ok(sse)).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    Ok(Sse::new(ReceiverStream::new(rx)).keep_alive(KeepAlive::default()))
+}
@@ -1,7 +1,25 @@
+use chrono::{DateTime, NaiveDate, Utc};
+
+pub mod admin;
 pub mod crypto;
 pub mod dashboard;
 pub mod ecommerce;
+pub mod events;
 pub mod news;
 pub mod scrapers;
 pub mod social;
 pub mod weather;
+
+/// Parse a range bound; a bare date means the start (or end) of that UTC day.
+pub(crate) fn parse_bound(raw: &str, end_of_day: bool) -> Option<DateTime<Utc>> {
+    if let Ok(ts) = DateTime::parse_from_rfc3339(raw) {
+        return Some(ts.with_timezone(&Utc));
+    }
+    let date = NaiveDate::parse_from_str(raw, "%Y-%m-%d").ok()?;
+    let time = if end_of_day {
+        date.and_hms_opt(23, 59, 59)?
+    } else {
+        date.and_hms_opt(0, 0, 0)?
+    };
+    Some(time.and_utc())
+}
@@ -1,22 +1,527 @@
-use axum::extract::{ConnectInfo, State};
-use axum::http::StatusCode;
+use axum::extract::{ConnectInfo, Path, Query, State};
+use axum::http::header::{CONTENT_TYPE, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+use axum::http::{HeaderMap, HeaderValue, StatusCode};
+use axum::response::{IntoResponse, Response};
 use axum::Json;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::cmp::Reverse;
 use std::net::SocketAddr;
 
+use super::parse_bound;
+use crate::analysis::entities::EntityKind;
+use crate::analysis::{clustering, entities, fnv1a};
 use crate::mock_data::news;
 use crate::state::AppState;
+use crate::syndication;
+
+/// Query parameters for `/api/news/feed`.
+#[derive(Debug, Deserialize)]
+pub struct FeedQuery {
+    pub category: Option<String>,
+    pub source: Option<String>,
+    pub author: Option<String>,
+    pub since: Option<String>,
+    pub until: Option<String>,
+    pub q: Option<String>,
+    /// Only `published_at` is supported; kept explicit for future sort keys.
+    pub sort: Option<String>,
+    #[serde(default)]
+    pub order: SortOrder,
+    pub limit: Option<usize>,
+    pub cursor: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    Asc,
+    #[default]
+    Desc,
+}
+
+const DEFAULT_PAGE_SIZE: usize = 50;
+const MAX_PAGE_SIZE: usize = 100;
 
 pub async fn get_feed(
     State(state): State<AppState>,
     ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    Query(query): Query<FeedQuery>,
 ) -> Result<Json<serde_json::Value>, StatusCode> {
     state
         .rate_limiter
         .check_rate_limit(&addr.ip().to_string(), "news/feed")?;
 
-    let articles = news::get_feed();
+    if query.sort.as_deref().is_some_and(|s| s != "published_at") {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    let limit = query.limit.unwrap_or(DEFAULT_PAGE_SIZE);
+    if limit == 0 || limit > MAX_PAGE_SIZE {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    let after = match query.cursor.as_deref() {
+        Some(raw) => Some(decode_cursor(raw).ok_or(StatusCode::BAD_REQUEST)?),
+        None => None,
+    };
+    let filter = news::ArticleFilter {
+        category: query.category,
+        source: query.source,
+        author: query.author,
+        since: match query.since.as_deref() {
+            Some(raw) => Some(parse_bound(raw, false).ok_or(StatusCode::BAD_REQUEST)?),
+            None => None,
+        },
+        until: match query.until.as_deref() {
+            Some(raw) => Some(parse_bound(raw, true).ok_or(StatusCode::BAD_REQUEST)?),
+            None => None,
+        },
+        q: query.q,
+    };
+
+    let articles: Vec<_> = current_feed(&state)?
+        .into_iter()
+        .filter(|a| filter.matches(a))
+        .collect();
+    let page = paginate(articles, query.order, after, limit);
+
+    Ok(Json(serde_json::json!({
+        "count": page.articles.len(),
+        "total": page.total,
+        "articles": page.articles,
+        "next_cursor": page.next_cursor,
+    })))
+}
+
+/// One page of the feed.
+struct FeedPage {
+    articles: Vec<news::NewsArticle>,
+    /// Articles matching the filter across all pages.
+    total: usize,
+    next_cursor: Option<String>,
+}
+
+/// Sort `articles` and take up to `limit` of them after the decoded cursor.
+fn paginate(
+    mut articles: Vec<news::NewsArticle>,
+    order: SortOrder,
+    after: Option<(DateTime<Utc>, String)>,
+    limit: usize,
+) -> FeedPage {
+    // Ties on the timestamp are broken by ID so the cursor position is exact.
+    articles.sort_by(|a, b| {
+        let ordering = (&a.published_at, &a.id).cmp(&(&b.published_at, &b.id));
+        match order {
+            SortOrder::Asc => ordering,
+            SortOrder::Desc => ordering.reverse(),
+        }
+    });
+    let total = articles.len();
+
+    let start = match &after {
+        Some((published_at, id)) => articles
+            .iter()
+            .position(|a| {
+                let key = (&a.published_at, &a.id);
+                match order {
+                    SortOrder::Asc => key > (published_at, id),
+                    SortOrder::Desc => key < (published_at, id),
+                }
+            })
+            .unwrap_or(total),
+        None => 0,
+    };
+    let page: Vec<_> = articles.into_iter().skip(start).take(limit).collect();
+    let next_cursor = (start + page.len() < total)
+        .then(|| page.last().map(|a| encode_cursor(&a.published_at, &a.id)))
+        .flatten();
+
+    FeedPage {
+        articles: page,
+        total,
+        next_cursor,
+    }
+}
+
+pub async fn get_categories(
+    State(state): State<AppState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    state
+        .rate_limiter
+        .check_rate_limit(&addr.ip().to_string(), "news/categories")?;
+
+    let categories = news::get_categories(&current_feed(&state)?);
+    Ok(Json(serde_json::json!({
+        "count": categories.len(),
+        "categories": categories,
+    })))
+}
+
+/// Query parameters for `/api/news/stories`.
+#[derive(Debug, Deserialize)]
+pub struct StoriesQuery {
+    pub threshold: Option<f64>,
+    /// Only return stories covered by at least this many articles.
+    pub min_articles: Option<usize>,
+}
+
+pub async fn get_stories(
+    State(state): State<AppState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    Query(query): Query<StoriesQuery>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    state
+        .rate_limiter
+        .check_rate_limit(&addr.ip().to_string(), "news/stories")?;
+
+    let threshold = query.threshold.unwrap_or(clustering::DEFAULT_THRESHOLD);
+    if !(0.0..=1.0).contains(&threshold) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    let min_articles = query.min_articles.unwrap_or(1);
+
+    let articles = current_feed(&state)?;
+    let stories: Vec<_> = clustering::cluster(&articles, threshold)
+        .into_iter()
+        .filter(|s| s.article_count >= min_articles)
+        .collect();
+    Ok(Json(serde_json::json!({
+        "count": stories.len(),
+        "article_count": articles.len(),
+        "threshold": threshold,
+        "stories": stories,
+    })))
+}
+
+/// Query parameters for `/api/news/entities`.
+#[derive(Debug, Deserialize)]
+pub struct EntitiesQuery {
+    pub kind: Option<EntityKind>,
+}
+
+pub async fn get_entities(
+    State(state): State<AppState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    Query(query): Query<EntitiesQuery>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    state
+        .rate_limiter
+        .check_rate_limit(&addr.ip().to_string(), "news/entities")?;
+
+    let entities: Vec<_> = entities::summarize(&current_feed(&state)?)
+        .into_iter()
+        .filter(|e| query.kind.is_none_or(|kind| e.kind == kind))
+        .collect();
     Ok(Json(serde_json::json!({
+        "count": entities.len(),
+        "entities": entities,
+    })))
+}
+
+/// Articles mentioning one entity, newest first. `name` may be the canonical
+/// name or any alias, e.g. `bitcoin`, `BTC` or `ecb`.
+pub async fn get_entity(
+    State(state): State<AppState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    Path(name): Path<String>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    state
+        .rate_limiter
+        .check_rate_limit(&addr.ip().to_string(), "news/entities/name")?;
+
+    let feed = current_feed(&state)?;
+    // Names outside the lexicon can still match people found by the honorific rule.
+    let (name, kind) = match entities::lookup(&name) {
+        Some(entry) => (entry.name.clone(), entry.kind),
+        None => feed
+            .iter()
+            .flat_map(|a| &a.entities)
+            .find(|e| entities::mentions_name(e, &name))
+            .map(|e| (e.name.clone(), e.kind))
+            .ok_or(StatusCode::NOT_FOUND)?,
+    };
+
+    let mut articles: Vec<_> = feed
+        .into_iter()
+        .filter(|a| entities::mentions(a, &name))
+        .collect();
+    articles.sort_by_key(|a| Reverse(a.published_at));
+    Ok(Json(serde_json::json!({
+        "entity": name,
+        "kind": kind,
         "count": articles.len(),
         "articles": articles,
     })))
 }
+
+/// Cursors are the hex-encoded sort key of the last article on the page.
+fn encode_cursor(published_at: &DateTime<Utc>, id: &str) -> String {
+    format!("{}|{id}", published_at.to_rfc3339())
+        .bytes()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+fn decode_cursor(cursor: &str) -> Option<(DateTime<Utc>, String)> {
+    if !cursor.len().is_multiple_of(2) {
+        return None;
+    }
+    let bytes = (0..cursor.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(cursor.get(i..i + 2)?, 16).ok())
+        .collect::<Option<Vec<u8>>>()?;
+    let text = String::from_utf8(bytes).ok()?;
+    let (published_at, id) = text.split_once('|')?;
+    let published_at = DateTime::parse_from_rfc3339(published_at).ok()?;
+    Some((published_at.to_utc(), id.to_string()))
+}
+
+/// Query parameters for the syndication feeds.
+#[derive(Debug, Deserialize)]
+pub struct SyndicationQuery {
+    pub category: Option<String>,
+}
+
+pub async fn get_rss(
+    State(state): State<AppState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    Query(query): Query<SyndicationQuery>,
+    headers: HeaderMap,
+) -> Result<Response, StatusCode> {
+    state
+        .rate_limiter
+        .check_rate_limit(&addr.ip().to_string(), "news/feed.rss")?;
+
+    let articles = filtered_feed(current_feed(&state)?, query.category.as_deref());
+    let body = syndication::render_rss(&articles, query.category.as_deref());
+    Ok(conditional_response(
+        &headers,
+        body,
+        "application/rss+xml; charset=utf-8",
+        syndication::last_modified(&articles),
+    ))
+}
+
+pub async fn get_atom(
+    State(state): State<AppState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    Query(query): Query<SyndicationQuery>,
+    headers: HeaderMap,
+) -> Result<Response, StatusCode> {
+    state
+        .rate_limiter
+        .check_rate_limit(&addr.ip().to_string(), "news/feed.atom")?;
+
+    let articles = filtered_feed(current_feed(&state)?, query.category.as_deref());
+    let body = syndication::render_atom(&articles, query.category.as_deref());
+    Ok(conditional_response(
+        &headers,
+        body,
+        "application/atom+xml; charset=utf-8",
+        syndication::last_modified(&articles),
+    ))
+}
+
+/// The served feed: the mock articles plus everything ingested so far.
+fn current_feed(state: &AppState) -> Result<Vec<news::NewsArticle>, StatusCode> {
+    state
+        .news_articles
+        .read()
+        .map(|articles| articles.clone())
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+fn filtered_feed(
+    mut articles: Vec<news::NewsArticle>,
+    category: Option<&str>,
+) -> Vec<news::NewsArticle> {
+    if let Some(category) = category {
+        articles.retain(|a| a.category.eq_ignore_ascii_case(category));
+    }
+    articles.sort_by_key(|a| Reverse(a.published_at));
+    articles
+}
+
+/// Build a feed response, answering `304 Not Modified` when the client's
+/// `If-None-Match` or `If-Modified-Since` shows it already has this version.
+fn conditional_response(
+    headers: &HeaderMap,
+    body: String,
+    content_type: &'static str,
+    last_modified: Option<DateTime<Utc>>,
+) -> Response {
+    let etag = format!("\"{:016x}\"", fnv1a(body.as_bytes()));
+    let last_modified = last_modified.map(|t| t.format("%a, %d %b %Y %H:%M:%S GMT").to_string());
+
+    let not_modified = match headers.get(IF_NONE_MATCH).and_then(|v| v.to_str().ok()) {
+        // If-None-Match takes precedence over If-Modified-Since (RFC 9110 §13.2.2).
+        Some(candidates) => candidates
+            .split(',')
+            .map(|c| c.trim().trim_start_matches("W/"))
+            .any(|c| c == etag || c == "*"),
+        None => headers
+            .get(IF_MODIFIED_SINCE)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| DateTime::parse_from_rfc2822(v).ok())
+            .zip(last_modified.as_deref())
+            .and_then(|(since, modified)| {
+                DateTime::parse_from_rfc2822(modified)
+                    .ok()
+                    .map(|m| m <= since)
+            })
+            .unwrap_or(false),
+    };
+
+    let mut response = if not_modified {
+        StatusCode::NOT_MODIFIED.into_response()
+    } else {
+        ([(CONTENT_TYPE, content_type)], body).into_response()
+    };
+
+    let response_headers = response.headers_mut();
+    if let Ok(value) = HeaderValue::from_str(&etag) {
+        response_headers.insert(ETAG, value);
+    }
+    if let Some(value) = last_modified.and_then(|v| HeaderValue::from_str(&v).ok()) {
+        response_headers.insert(LAST_MODIFIED, value);
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MODIFIED: &str = "Mon, 19 Oct 2026 07:15:00 GMT";
+
+    fn get(headers: &[(axum::http::HeaderName, &str)]) -> Response {
+        let mut map = HeaderMap::new();
+        for (name, value) in headers {
+            map.insert(name, HeaderValue::from_str(value).unwrap());
+        }
+        let modified = DateTime::parse_from_rfc2822(MODIFIED)
+            .unwrap()
+            .with_timezone(&Utc);
+        conditional_response(
+            &map,
+            "<rss/>".to_string(),
+            "application/rss+xml",
+            Some(modified),
+        )
+    }
+
+    fn ids(page: &FeedPage) -> Vec<&str> {
+        page.articles.iter().map(|a| a.id.as_str()).collect()
+    }
+
+    #[test]
+    fn pages_through_the_feed_with_cursors() {
+        let feed = news::get_feed();
+        let total = feed.len();
+        let mut seen = Vec::new();
+        let mut after = None;
+        loop {
+            let page = paginate(feed.clone(), SortOrder::Desc, after, 10);
+            assert_eq!(page.total, total);
+            seen.extend(ids(&page).into_iter().map(str::to_string));
+            match page.next_cursor {
+                Some(cursor) => after = Some(decode_cursor(&cursor).unwrap()),
+                None => {
+                    // The last page is short and says so by having no cursor.
+                    assert_eq!(page.articles.len(), (total - 1) % 10 + 1);
+                    break;
+                }
+            }
+        }
+        let everything = paginate(feed.clone(), SortOrder::Desc, None, total);
+        assert_eq!(seen, ids(&everything));
+        assert!(everything.next_cursor.is_none());
+
+        let ascending = paginate(feed.clone(), SortOrder::Asc, None, total);
+        let mut reversed = ids(&ascending);
+        reversed.reverse();
+        assert_eq!(reversed, ids(&everything));
+    }
+
+    #[test]
+    fn cursor_past_the_end_and_empty_feeds_give_empty_pages() {
+        let feed = news::get_feed();
+        let oldest = paginate(feed.clone(), SortOrder::Asc, None, 1).articles[0].clone();
+        let past_end = Some((oldest.published_at, oldest.id));
+        let page = paginate(feed, SortOrder::Desc, past_end, 10);
+        assert!(page.articles.is_empty() && page.next_cursor.is_none());
+
+        let empty = paginate(Vec::new(), SortOrder::Desc, None, 10);
+        assert_eq!((empty.total, empty.next_cursor), (0, None));
+    }
+
+    #[test]
+    fn rejects_malformed_cursors() {
+        let published_at = "2026-10-19T07:15:00Z".parse().unwrap();
+        let cursor = encode_cursor(&published_at, "news-1");
+        assert_eq!(
+            decode_cursor(&cursor),
+            Some((published_at, "news-1".into()))
+        );
+        assert_eq!(decode_cursor("abc"), None);
+        assert_eq!(decode_cursor("zz"), None);
+        assert_eq!(decode_cursor("é1"), None);
+        // Valid hex, but not a `published_at|id` pair.
+        assert_eq!(decode_cursor("6869"), None);
+        assert_eq!(decode_cursor("787c6e6577732d31"), None);
+        assert_eq!(decode_cursor("ff7c"), None);
+    }
+
+    #[test]
+    fn answers_conditional_gets_with_304() {
+        let fresh = get(&[]);
+        assert_eq!(fresh.status(), StatusCode::OK);
+        let etag = fresh.headers()[ETAG].to_str().unwrap().to_string();
+        // FNV-1a of the body, so the tag survives restarts and upgrades.
+        assert_eq!(etag, format!("\"{:016x}\"", fnv1a(b"<rss/>")));
+        assert_eq!(fresh.headers()[LAST_MODIFIED], MODIFIED);
+
+        let weak_list = format!("\"other\", W/{etag}");
+        assert_eq!(
+            get(&[(IF_NONE_MATCH, &etag)]).status(),
+            StatusCode::NOT_MODIFIED
+        );
+        assert_eq!(
+            get(&[(IF_NONE_MATCH, &weak_list)]).status(),
+            StatusCode::NOT_MODIFIED
+        );
+        assert_eq!(
+            get(&[(IF_NONE_MATCH, "*")]).status(),
+            StatusCode::NOT_MODIFIED
+        );
+        assert_eq!(
+            get(&[(IF_NONE_MATCH, "\"stale\"")]).status(),
+            StatusCode::OK
+        );
+
+        let later = "Tue, 20 Oct 2026 00:00:00 GMT";
+        let earlier = "Sun, 18 Oct 2026 00:00:00 GMT";
+        assert_eq!(
+            get(&[(IF_MODIFIED_SINCE, MODIFIED)]).status(),
+            StatusCode::NOT_MODIFIED
+        );
+        assert_eq!(
+            get(&[(IF_MODIFIED_SINCE, later)]).status(),
+            StatusCode::NOT_MODIFIED
+        );
+        assert_eq!(
+            get(&[(IF_MODIFIED_SINCE, earlier)]).status(),
+            StatusCode::OK
+        );
+        assert_eq!(
+            get(&[(IF_MODIFIED_SINCE, "garbage")]).status(),
+            StatusCode::OK
+        );
+        // A mismatched tag wins over a date that would otherwise match.
+        let both = [(IF_NONE_MATCH, "\"stale\""), (IF_MODIFIED_SINCE, later)];
+        assert_eq!(get(&both).status(), StatusCode::OK);
+
+        let not_modified = get(&[(IF_NONE_MATCH, &etag)]);
+        assert_eq!(not_modified.headers()[ETAG], etag.as_str());
+    }
+}
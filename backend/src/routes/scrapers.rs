@@ -1,17 +1,21 @@
 use axum::extract::{ConnectInfo, Path, State};
-use axum::http::StatusCode;
-use axum::response::sse::{Event, KeepAlive, Sse};
-use axum::response::IntoResponse;
+use axum::http::{HeaderMap, StatusCode};
 use axum::Json;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use rand::Rng;
 use serde::Serialize;
-use std::convert::Infallible;
+use std::collections::HashSet;
 use std::net::SocketAddr;
-use std::time::Duration;
-use tokio_stream::wrappers::ReceiverStream;
+use std::sync::{Arc, RwLock};
 
+use crate::extract::{feed, posts, product_page};
+use crate::mock_data::cities;
+use crate::mock_data::news::{self, NewsArticle};
+use crate::mock_data::social::{self, SocialPost, Topic, TrendSort};
+use crate::routes::admin;
 use crate::state::AppState;
+use crate::storage::records::{self, ProductPrice, SentimentPoint};
+use crate::storage::Store;
 
 #[derive(Debug, Serialize)]
 pub struct ScraperInfo {
@@ -26,14 +30,24 @@ pub struct ScraperInfo {
     pub avg_duration_secs: u32,
 }
 
+/// The outcome of a scraper run.
 #[derive(Debug, Serialize)]
-pub struct ScraperProgress {
+pub struct ScraperRun {
+    pub id: String,
+    pub name: String,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+    /// Records found by the end of the run.
+    pub records_found: u32,
+    pub steps: Vec<ScraperStep>,
+}
+
+/// One step of a scraper run, with the records found so far.
+#[derive(Debug, Serialize)]
+pub struct ScraperStep {
     pub step: u32,
-    pub total_steps: u32,
     pub message: String,
-    pub progress_percent: f64,
     pub records_found: u32,
-    pub status: String,
 }
 
 pub async fn get_status(
@@ -51,7 +65,7 @@ pub async fn get_status(
         ("scraper-001", "E-Commerce Price Tracker", "running", 45_230, 99.2, "ecommerce", "Every 6h", 180),
         ("scraper-002", "Social Media Trends", "running", 128_400, 97.8, "social", "Every 1h", 45),
         ("scraper-003", "News Aggregator", "running", 67_890, 99.5, "news", "Every 30m", 30),
-        ("scraper-004", "Crypto Market Data", "running", 312_000, 99.9, "crypto", "Every 5m", 8),
+        ("scraper-004", "Crypto Market Data", "running", 312_000, 99.9, "crypto", "Every 1m", 8),
         ("scraper-005", "Weather Data Collector", "running", 89_100, 98.7, "weather", "Every 1h", 20),
         ("scraper-006", "Job Listings Monitor", "paused", 23_450, 96.3, "jobs", "Every 12h", 300),
         ("scraper-007", "Real Estate Tracker", "paused", 15_670, 94.1, "realestate", "Every 24h", 600),
@@ -70,7 +84,7 @@ pub async fn get_status(
                 status: status.to_string(),
                 last_run: last_run.to_rfc3339(),
                 data_count: (count as f64 * rng.gen_range(0.98..1.02)) as u64,
-                success_rate: ((rate as f64 + rng.gen_range(-0.5_f64..0.5)) * 10.0).round() / 10.0,
+                success_rate: ((rate + rng.gen_range(-0.5_f64..0.5)) * 10.0).round() / 10.0,
                 category: cat.to_string(),
                 schedule: sched.to_string(),
                 avg_duration_secs: dur,
@@ -81,17 +95,21 @@ pub async fn get_status(
     Ok(Json(infos))
 }
 
+/// Run a scraper to completion and return every step it took. Requires the
+/// `ADMIN_TOKEN` as a bearer token, like the other admin endpoints, since a
+/// run reads the configured sources and writes to the store.
 pub async fn start_scraper(
     State(state): State<AppState>,
     ConnectInfo(addr): ConnectInfo<SocketAddr>,
     Path(id): Path<String>,
-) -> Result<impl IntoResponse, StatusCode> {
+    headers: HeaderMap,
+) -> Result<Json<ScraperRun>, StatusCode> {
     state
         .rate_limiter
         .check_rate_limit(&addr.ip().to_string(), "scrapers/start")?;
+    admin::authorize(state.admin.token.as_deref(), &headers)?;
 
-    let (tx, rx) = tokio::sync::mpsc::channel::<Result<Event, Infallible>>(32);
-
+    let started_at = Utc::now();
     let scraper_name = match id.as_str() {
         "scraper-001" => "E-Commerce Price Tracker",
         "scraper-002" => "Social Media Trends",
@@ -106,14 +124,69 @@ pub async fn start_scraper(
 
     let name = scraper_name.to_string();
 
-    // Build the steps outside of the spawned task so that the non-Send
-    // ThreadRng is not held across .await points.
-    let total_records: u32 = {
-        let mut rng = rand::thread_rng();
-        rng.gen_range(150..400)
+    let steps = match id.as_str() {
+        "scraper-001" => {
+            let dir = state.sources.product_pages_dir.clone();
+            let store = Arc::clone(&state.store);
+            tokio::task::spawn_blocking(move || product_page_steps(&name, &dir, &store))
+                .await
+                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        }
+        "scraper-002" => {
+            let files = state.sources.social_posts.clone();
+            let posts = Arc::clone(&state.social_posts);
+            let store = Arc::clone(&state.store);
+            tokio::task::spawn_blocking(move || social_post_steps(&name, &files, &posts, &store))
+                .await
+                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        }
+        "scraper-003" => {
+            let feeds = state.sources.news_feeds.clone();
+            let articles = Arc::clone(&state.news_articles);
+            let store = Arc::clone(&state.store);
+            tokio::task::spawn_blocking(move || news_feed_steps(&name, &feeds, &articles, &store))
+                .await
+                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        }
+        "scraper-004" => {
+            let store = Arc::clone(&state.store);
+            tokio::task::spawn_blocking(move || crypto_steps(&name, &store))
+                .await
+                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        }
+        "scraper-005" => {
+            let store = Arc::clone(&state.store);
+            tokio::task::spawn_blocking(move || weather_steps(&name, &store))
+                .await
+                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        }
+        _ => simulated_steps(&name),
     };
 
-    let steps: Vec<(String, u32)> = vec![
+    let records_found = steps.last().map_or(0, |(_, records)| *records);
+    Ok(Json(ScraperRun {
+        id,
+        name: scraper_name.to_string(),
+        started_at,
+        finished_at: Utc::now(),
+        records_found,
+        steps: steps
+            .into_iter()
+            .zip(1..)
+            .map(|((message, records_found), step)| ScraperStep {
+                step,
+                message,
+                records_found,
+            })
+            .collect(),
+    }))
+}
+
+/// Steps for a scraper without a real data source yet.
+fn simulated_steps(name: &str) -> Vec<(String, u32)> {
+    let total_records: u32 = rand::thread_rng().gen_range(150..400);
+
+    vec![
         (format!("Initializing {}...", name), 0),
         ("Connecting to data source...".to_string(), 0),
         ("Authenticating session...".to_string(), 0),
@@ -122,36 +195,337 @@ pub async fn start_scraper(
         ("Fetching page 3/5...".to_string(), total_records * 3 / 5),
         ("Fetching page 4/5...".to_string(), total_records * 4 / 5),
         ("Fetching page 5/5...".to_string(), total_records),
-        (format!("Processing {} records...", total_records), total_records),
-        (format!("Validating data integrity..."), total_records),
-        (format!("Complete: {} new records stored", total_records), total_records),
-    ];
+        (
+            format!("Processing {} records...", total_records),
+            total_records,
+        ),
+        ("Validating data integrity...".to_string(), total_records),
+        (
+            format!("Complete: {} new records stored", total_records),
+            total_records,
+        ),
+    ]
+}
 
-    let total_steps = steps.len() as u32;
+/// Steps for the price tracker: extract every saved product page in `dir`
+/// and record the prices found.
+fn product_page_steps(name: &str, dir: &std::path::Path, store: &Store) -> Vec<(String, u32)> {
+    let mut steps = vec![(
+        format!(
+            "Initializing {} with rules for {} sites...",
+            name,
+            product_page::site_rules().len()
+        ),
+        0,
+    )];
 
-    tokio::spawn(async move {
-        for (i, (message, records)) in steps.into_iter().enumerate() {
-            let step_num = (i + 1) as u32;
-            let progress = ScraperProgress {
-                step: step_num,
-                total_steps,
-                message,
-                progress_percent: (step_num as f64 / total_steps as f64 * 100.0).round(),
-                records_found: records,
-                status: if step_num == total_steps {
-                    "complete".to_string()
-                } else {
-                    "running".to_string()
-                },
-            };
-
-            let data = serde_json::to_string(&progress).unwrap();
-            if tx.send(Ok(Event::default().data(data))).await.is_err() {
-                break;
+    let pages = match product_page::extract_dir(dir) {
+        Ok(pages) => pages,
+        Err(err) => {
+            steps.push((format!("Failed: {} ({})", err, dir.display()), 0));
+            return steps;
+        }
+    };
+
+    let now = Utc::now();
+    let mut prices = Vec::new();
+    let mut found = 0;
+    for page in pages {
+        let message = match page.result {
+            Ok(product) => {
+                found += 1;
+                prices.push(ProductPrice {
+                    product_id: product.id.clone(),
+                    source: product.source.clone(),
+                    timestamp: now,
+                    price: product.price,
+                    currency: product.currency.clone(),
+                    in_stock: product.in_stock,
+                });
+                format!(
+                    "Parsed {}: {} @ {:.2} {} ({})",
+                    page.file,
+                    product.name,
+                    product.price,
+                    product.currency,
+                    if product.in_stock {
+                        "in stock"
+                    } else {
+                        "out of stock"
+                    }
+                )
+            }
+            Err(err) => format!("Skipped {}: {}", page.file, err),
+        };
+        steps.push((message, found));
+    }
+
+    let message = match store.append_new(&records::PRODUCT_PRICES, &prices) {
+        Ok(stored) => format!("Stored {} price observations", stored),
+        Err(err) => format!("Failed to store prices: {}", err),
+    };
+    steps.push((message, found));
+
+    steps.push((format!("Complete: {} products extracted", found), found));
+    steps
+}
+
+/// Malformed lines listed per export before the rest are only counted.
+const MAX_REPORTED_REJECTS: usize = 3;
+
+/// Steps for the social media trends scraper: ingest every configured NDJSON
+/// export and, if any file yields posts, replace the current posts with them
+/// and persist the trending hashtags' hourly sentiment.
+fn social_post_steps(
+    name: &str,
+    files: &[String],
+    social_posts: &RwLock<Vec<SocialPost>>,
+    store: &Store,
+) -> Vec<(String, u32)> {
+    let mut steps = vec![(
+        format!("Initializing {} with {} exports...", name, files.len()),
+        0,
+    )];
+
+    let ingested = posts::ingest(files);
+    let mut found = 0;
+    for file in &ingested {
+        let message = match &file.result {
+            Ok(batch) => {
+                found += batch.posts.len() as u32;
+                let hashtags: usize = batch.posts.iter().map(|p| p.hashtags.len()).sum();
+                format!(
+                    "Parsed {}: {} posts, {} hashtags, {} lines skipped",
+                    file.location,
+                    batch.posts.len(),
+                    hashtags,
+                    batch.rejected.len()
+                )
+            }
+            Err(err) => format!("Skipped {}: {}", file.location, err),
+        };
+        steps.push((message, found));
+        if let Ok(batch) = &file.result {
+            for rejected in batch.rejected.iter().take(MAX_REPORTED_REJECTS) {
+                steps.push((
+                    format!("  line {}: {}", rejected.line, rejected.reason),
+                    found,
+                ));
+            }
+        }
+    }
+
+    let merged = posts::merge(&ingested);
+    let stored = merged.len() as u32;
+    if !merged.is_empty() {
+        let points: Vec<SentimentPoint> = social::get_trends(&merged, TrendSort::Mentions, None)
+            .iter()
+            .map(|trend| Topic::for_hashtag(&trend.hashtag))
+            .flat_map(|topic| {
+                let sentiment = social::get_sentiment(&merged, &topic);
+                sentiment
+                    .data_points
+                    .into_iter()
+                    .filter(|point| point.mention_count > 0)
+                    .filter_map(move |point| {
+                        Some(SentimentPoint {
+                            hashtag: topic.hashtag.clone(),
+                            timestamp: point.timestamp.parse().ok()?,
+                            positive: point.positive,
+                            negative: point.negative,
+                            neutral: point.neutral,
+                            mentions: point.mention_count,
+                        })
+                    })
+            })
+            .collect();
+        let message = match store.append_new(&records::SENTIMENT, &points) {
+            Ok(stored) => format!("Stored {} new hourly sentiment points", stored),
+            Err(err) => format!("Failed to store sentiment: {}", err),
+        };
+        steps.push((message, stored));
+        if let Ok(mut current) = social_posts.write() {
+            *current = merged;
+        }
+    }
+
+    steps.push((format!("Complete: {} posts ingested", stored), stored));
+    steps
+}
+
+/// Steps for the crypto collector: record one tick of every coin's price.
+fn crypto_steps(name: &str, store: &Store) -> Vec<(String, u32)> {
+    let mut steps = vec![(format!("Initializing {}...", name), 0)];
+    match records::record_prices(store, Utc::now()) {
+        Ok(recorded) => {
+            let recorded = recorded as u32;
+            steps.push((
+                format!("Complete: {} price ticks stored", recorded),
+                recorded,
+            ));
+        }
+        Err(err) => steps.push((format!("Failed: {}", err), 0)),
+    }
+    steps
+}
+
+/// Cities reported per progress step by the weather collector.
+const CITIES_PER_STEP: usize = 10;
+
+/// Steps for the weather collector: record every city's days since its last
+/// recorded one, up to and including today so far.
+fn weather_steps(name: &str, store: &Store) -> Vec<(String, u32)> {
+    let all = cities::cities();
+    let mut steps = vec![(
+        format!("Initializing {} for {} cities...", name, all.len()),
+        0,
+    )];
+
+    let now = Utc::now();
+    let mut recorded = 0;
+    for batch in all.chunks(CITIES_PER_STEP) {
+        let mut days = 0;
+        for city in batch {
+            match records::record_weather(store, city, now) {
+                Ok(written) => days += written as u32,
+                Err(err) => {
+                    steps.push((format!("Failed: {}", err), recorded + days));
+                    return steps;
+                }
             }
-            tokio::time::sleep(Duration::from_millis(500)).await;
         }
-    });
+        recorded += days;
+        let names: Vec<&str> = batch.iter().map(|city| city.name.as_str()).collect();
+        steps.push((
+            format!("Recorded {}: {} days", names.join(", "), days),
+            recorded,
+        ));
+    }
+
+    steps.push((
+        format!("Complete: {} observations stored", recorded),
+        recorded,
+    ));
+    steps
+}
+
+/// Steps for the news aggregator: ingest every configured RSS/Atom feed,
+/// persist the articles not stored yet and add them to the served feed.
+fn news_feed_steps(
+    name: &str,
+    feeds: &[String],
+    news_articles: &RwLock<Vec<NewsArticle>>,
+    store: &Store,
+) -> Vec<(String, u32)> {
+    let mut steps = vec![(
+        format!("Initializing {} with {} feeds...", name, feeds.len()),
+        0,
+    )];
+
+    let mut found = 0;
+    for ingested in feed::ingest(feeds, Utc::now()) {
+        let message = match ingested.result {
+            // Articles seen before keep their stored copy, so an undated one
+            // keeps the time it was first seen.
+            Ok(feed) => match store.append_new(&records::ARTICLES, &unseen(news_articles, &feed)) {
+                Ok(stored) => {
+                    found += feed.articles.len() as u32;
+                    if let Ok(mut current) = news_articles.write() {
+                        news::merge(&mut current, feed.articles.clone());
+                    }
+                    format!(
+                        "Parsed {}: {} articles ({} new) from {}",
+                        ingested.location,
+                        feed.articles.len(),
+                        stored,
+                        feed.title
+                    )
+                }
+                Err(err) => format!("Could not store {}: {}", ingested.location, err),
+            },
+            Err(err) => format!("Skipped {}: {}", ingested.location, err),
+        };
+        steps.push((message, found));
+    }
+
+    steps.push((format!("Complete: {} articles ingested", found), found));
+    steps
+}
+
+/// The articles of `feed` whose IDs are not in `news_articles` yet.
+fn unseen(news_articles: &RwLock<Vec<NewsArticle>>, feed: &feed::Feed) -> Vec<NewsArticle> {
+    let Ok(current) = news_articles.read() else {
+        return feed.articles.clone();
+    };
+    let known: HashSet<&str> = current.iter().map(|a| a.id.as_str()).collect();
+    feed.articles
+        .iter()
+        .filter(|a| !known.contains(a.id.as_str()))
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AdminConfig;
+    use axum::http::header::AUTHORIZATION;
+    use axum::http::HeaderValue;
+
+    async fn start(
+        state: &AppState,
+        id: &str,
+        token: Option<&str>,
+    ) -> Result<ScraperRun, StatusCode> {
+        let mut headers = HeaderMap::new();
+        if let Some(token) = token {
+            let value = HeaderValue::from_str(&format!("Bearer {token}")).unwrap();
+            headers.insert(AUTHORIZATION, value);
+        }
+        start_scraper(
+            State(state.clone()),
+            ConnectInfo(([127, 0, 0, 1], 4000).into()),
+            Path(id.to_string()),
+            headers,
+        )
+        .await
+        .map(|Json(run)| run)
+    }
+
+    #[tokio::test]
+    async fn runs_need_the_admin_token() {
+        let mut state = AppState::scratch("scrapers-auth");
+        assert_eq!(
+            start(&state, "scraper-005", None).await.unwrap_err(),
+            StatusCode::NOT_FOUND
+        );
+        state.admin = Arc::new(AdminConfig {
+            token: Some("secret".to_string()),
+        });
+        for token in [None, Some("wrong")] {
+            assert_eq!(
+                start(&state, "scraper-005", token).await.unwrap_err(),
+                StatusCode::UNAUTHORIZED
+            );
+        }
+        assert!(records::usage(&state.store)
+            .unwrap()
+            .iter()
+            .all(|series| series.bytes == 0));
+    }
 
-    Ok(Sse::new(ReceiverStream::new(rx)).keep_alive(KeepAlive::default()))
+    #[tokio::test]
+    async fn runs_report_the_steps_they_took() {
+        let mut state = AppState::scratch("scrapers-run");
+        state.admin = Arc::new(AdminConfig {
+            token: Some("secret".to_string()),
+        });
+        let run = start(&state, "scraper-005", Some("secret")).await.unwrap();
+        assert_eq!(run.name, "Weather Data Collector");
+        assert!(run.started_at <= run.finished_at);
+        let numbers: Vec<u32> = run.steps.iter().map(|step| step.step).collect();
+        assert_eq!(numbers, (1..=run.steps.len() as u32).collect::<Vec<_>>());
+        assert_eq!(run.records_found, run.steps.last().unwrap().records_found);
+        assert_eq!(run.records_found as usize, cities::cities().len());
+    }
 }
@@ -1,26 +1,70 @@
-use axum::extract::{ConnectInfo, Path, State};
+use axum::extract::{ConnectInfo, Path, Query, State};
 use axum::http::StatusCode;
 use axum::Json;
+use chrono::{DateTime, Duration, TimeDelta, Utc};
+use serde::{Deserialize, Serialize};
 use std::net::SocketAddr;
+use std::sync::Arc;
 
+use super::parse_bound;
+use crate::analysis::sentiment;
+use crate::extract::posts;
 use crate::mock_data::social;
 use crate::state::AppState;
+use crate::storage::{self, records};
+
+/// Query parameters for `/api/social/trends`.
+#[derive(Debug, Deserialize)]
+pub struct TrendsQuery {
+    #[serde(default)]
+    pub sort: social::TrendSort,
+    pub platform: Option<String>,
+}
 
 pub async fn get_trends(
     State(state): State<AppState>,
     ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    Query(query): Query<TrendsQuery>,
 ) -> Result<Json<serde_json::Value>, StatusCode> {
     state
         .rate_limiter
         .check_rate_limit(&addr.ip().to_string(), "social/trends")?;
 
-    let trends = social::get_trends();
+    let trends = {
+        let posts = state
+            .social_posts
+            .read()
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        let platform = query.platform.as_deref().map(posts::normalize_platform);
+        social::get_trends(&posts, query.sort, platform.as_deref())
+    };
     Ok(Json(serde_json::json!({
         "count": trends.len(),
         "trends": trends,
     })))
 }
 
+pub async fn get_emerging(
+    State(state): State<AppState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    state
+        .rate_limiter
+        .check_rate_limit(&addr.ip().to_string(), "social/emerging")?;
+
+    let emerging = {
+        let posts = state
+            .social_posts
+            .read()
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        social::get_emerging(&posts)
+    };
+    Ok(Json(serde_json::json!({
+        "count": emerging.len(),
+        "topics": emerging,
+    })))
+}
+
 pub async fn get_sentiment(
     State(state): State<AppState>,
     ConnectInfo(addr): ConnectInfo<SocketAddr>,
@@ -30,6 +74,247 @@ pub async fn get_sentiment(
         .rate_limiter
         .check_rate_limit(&addr.ip().to_string(), "social/sentiment")?;
 
-    let sentiment = social::get_sentiment(&topic);
+    let posts = state
+        .social_posts
+        .read()
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let topic = social::resolve_topic(&posts, &topic).ok_or(StatusCode::NOT_FOUND)?;
+    let sentiment = social::get_sentiment(&posts, &topic);
     Ok(Json(serde_json::json!(sentiment)))
 }
+
+/// Bucket size for `/api/social/sentiment/{topic}/history`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SentimentInterval {
+    /// Every recorded hour.
+    #[default]
+    Hour,
+    Day,
+}
+
+/// Query parameters for `/api/social/sentiment/{topic}/history`.
+///
+/// `from` and `to` accept either `YYYY-MM-DD` or RFC 3339 timestamps.
+#[derive(Debug, Deserialize)]
+pub struct SentimentHistoryQuery {
+    pub from: Option<String>,
+    pub to: Option<String>,
+    #[serde(default)]
+    pub interval: SentimentInterval,
+}
+
+/// Longest window, in days, that may be requested for each interval.
+const MAX_HOURLY_WINDOW_DAYS: i64 = 31;
+const MAX_DAILY_WINDOW_DAYS: i64 = 366;
+
+/// Hourly sentiment recorded for a topic by social ingestion, or daily
+/// points weighted by mentions. Defaults to the last 24 hours, or 30 days of
+/// daily points.
+pub async fn get_sentiment_history(
+    State(state): State<AppState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    Path(topic): Path<String>,
+    Query(query): Query<SentimentHistoryQuery>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    state
+        .rate_limiter
+        .check_rate_limit(&addr.ip().to_string(), "social/sentiment-history")?;
+
+    let topic = {
+        let posts = state
+            .social_posts
+            .read()
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        social::resolve_topic(&posts, &topic).ok_or(StatusCode::NOT_FOUND)?
+    };
+    let (from, to) = sentiment_window(&query, Utc::now())?;
+    let store = Arc::clone(&state.store);
+    let hashtag = topic.hashtag.clone();
+    let interval = query.interval;
+    let points = tokio::task::spawn_blocking(move || {
+        store
+            .query(&records::SENTIMENT, Some(&hashtag), from, to)
+            .map(|points| match interval {
+                SentimentInterval::Hour => points,
+                SentimentInterval::Day => storage::downsample(points, TimeDelta::days(1)),
+            })
+    })
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(serde_json::json!({
+        "topic": topic.id,
+        "hashtag": topic.hashtag,
+        "from": from.to_rfc3339(),
+        "to": to.to_rfc3339(),
+        "interval": query.interval,
+        "data_points": points.len(),
+        "sentiment": points,
+    })))
+}
+
+/// Resolve the requested window, clamping `to` at `now`. Hourly windows may
+/// span at most `MAX_HOURLY_WINDOW_DAYS` and daily ones
+/// `MAX_DAILY_WINDOW_DAYS`.
+fn sentiment_window(
+    query: &SentimentHistoryQuery,
+    now: DateTime<Utc>,
+) -> Result<(DateTime<Utc>, DateTime<Utc>), StatusCode> {
+    let to = match query.to.as_deref() {
+        Some(raw) => parse_bound(raw, true)
+            .ok_or(StatusCode::BAD_REQUEST)?
+            .min(now),
+        None => now,
+    };
+    let from = match query.from.as_deref() {
+        Some(raw) => parse_bound(raw, false).ok_or(StatusCode::BAD_REQUEST)?,
+        None if query.interval == SentimentInterval::Day => to - Duration::days(30),
+        None => to - Duration::hours(24),
+    };
+    let max_days = match query.interval {
+        SentimentInterval::Hour => MAX_HOURLY_WINDOW_DAYS,
+        SentimentInterval::Day => MAX_DAILY_WINDOW_DAYS,
+    };
+    if from > to || to - from > Duration::days(max_days) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    Ok((from, to))
+}
+
+/// Query parameters for `/api/social/platforms`.
+#[derive(Debug, Deserialize)]
+pub struct PlatformsQuery {
+    pub topic: Option<String>,
+}
+
+pub async fn get_platforms(
+    State(state): State<AppState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    Query(query): Query<PlatformsQuery>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    state
+        .rate_limiter
+        .check_rate_limit(&addr.ip().to_string(), "social/platforms")?;
+
+    let posts = state
+        .social_posts
+        .read()
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let topic = match query.topic.as_deref() {
+        Some(raw) => Some(social::resolve_topic(&posts, raw).ok_or(StatusCode::NOT_FOUND)?),
+        None => None,
+    };
+    let summary = social::get_platforms(&posts, topic.as_ref());
+    Ok(Json(serde_json::json!(summary)))
+}
+
+/// Query parameters for `/api/social/graph`.
+#[derive(Debug, Deserialize)]
+pub struct GraphQuery {
+    pub topic: Option<String>,
+    pub depth: Option<usize>,
+}
+
+const DEFAULT_GRAPH_DEPTH: usize = 1;
+const MAX_GRAPH_DEPTH: usize = 3;
+
+pub async fn get_graph(
+    State(state): State<AppState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    Query(query): Query<GraphQuery>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    state
+        .rate_limiter
+        .check_rate_limit(&addr.ip().to_string(), "social/graph")?;
+
+    let depth = graph_depth(query.depth)?;
+    let posts = state
+        .social_posts
+        .read()
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let topic = match query.topic.as_deref() {
+        Some(raw) => Some(social::resolve_topic(&posts, raw).ok_or(StatusCode::NOT_FOUND)?),
+        None => None,
+    };
+    let graph = social::get_graph(&posts, topic.as_ref(), depth);
+    Ok(Json(serde_json::json!(graph)))
+}
+
+/// The requested hop count, which must lie within `1..=MAX_GRAPH_DEPTH`.
+fn graph_depth(depth: Option<usize>) -> Result<usize, StatusCode> {
+    match depth.unwrap_or(DEFAULT_GRAPH_DEPTH) {
+        depth @ 1..=MAX_GRAPH_DEPTH => Ok(depth),
+        _ => Err(StatusCode::BAD_REQUEST),
+    }
+}
+
+/// Longest text accepted by `/api/social/sentiment/analyze`.
+const MAX_ANALYZE_CHARS: usize = 10_000;
+
+/// Body of `POST /api/social/sentiment/analyze`.
+#[derive(Debug, Deserialize)]
+pub struct AnalyzeRequest {
+    pub text: String,
+}
+
+pub async fn analyze_sentiment(
+    State(state): State<AppState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    Json(request): Json<AnalyzeRequest>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    state
+        .rate_limiter
+        .check_rate_limit(&addr.ip().to_string(), "social/sentiment/analyze")?;
+
+    if request.text.trim().is_empty() || request.text.chars().count() > MAX_ANALYZE_CHARS {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    Ok(Json(serde_json::json!(sentiment::score(&request.text))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn limits_graph_depth() {
+        assert_eq!(graph_depth(None), Ok(DEFAULT_GRAPH_DEPTH));
+        assert_eq!(graph_depth(Some(MAX_GRAPH_DEPTH)), Ok(MAX_GRAPH_DEPTH));
+        assert_eq!(graph_depth(Some(0)), Err(StatusCode::BAD_REQUEST));
+        assert_eq!(
+            graph_depth(Some(MAX_GRAPH_DEPTH + 1)),
+            Err(StatusCode::BAD_REQUEST)
+        );
+    }
+
+    #[test]
+    fn limits_the_sentiment_window() {
+        let now: DateTime<Utc> = "2026-03-31T12:00:00Z".parse().unwrap();
+        let query = |from: &str, interval| SentimentHistoryQuery {
+            from: Some(from.to_string()),
+            to: None,
+            interval,
+        };
+        let window = |from, interval| sentiment_window(&query(from, interval), now);
+
+        assert_eq!(
+            window("2026-03-01T12:00:00Z", SentimentInterval::Hour),
+            Ok(("2026-03-01T12:00:00Z".parse().unwrap(), now))
+        );
+        assert_eq!(
+            window("2026-02-28", SentimentInterval::Hour),
+            Err(StatusCode::BAD_REQUEST)
+        );
+        assert!(window("2025-04-01", SentimentInterval::Day).is_ok());
+        assert_eq!(
+            window("2025-01-01", SentimentInterval::Day),
+            Err(StatusCode::BAD_REQUEST)
+        );
+        assert_eq!(
+            window("2026-04-01", SentimentInterval::Day),
+            Err(StatusCode::BAD_REQUEST)
+        );
+    }
+}
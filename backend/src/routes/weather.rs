@@ -1,20 +1,308 @@
-use axum::extract::{ConnectInfo, Path, State};
+use axum::extract::{ConnectInfo, Path, Query, State};
 use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
 use axum::Json;
+use chrono::{Datelike, NaiveDate, TimeDelta, Utc};
+use serde::Deserialize;
 use std::net::SocketAddr;
+use std::sync::Arc;
 
-use crate::mock_data::weather;
+use crate::mock_data::{cities, weather};
 use crate::state::AppState;
+use crate::storage::records;
+
+/// Query parameters for `/api/weather/{city}`.
+#[derive(Debug, Deserialize)]
+pub struct WeatherQuery {
+    #[serde(default)]
+    pub units: weather::Units,
+}
 
 pub async fn get_weather(
     State(state): State<AppState>,
     ConnectInfo(addr): ConnectInfo<SocketAddr>,
     Path(city): Path<String>,
-) -> Result<Json<serde_json::Value>, StatusCode> {
+    Query(query): Query<WeatherQuery>,
+) -> Result<Response, StatusCode> {
     state
         .rate_limiter
         .check_rate_limit(&addr.ip().to_string(), "weather")?;
 
-    let data = weather::get_weather(&city);
-    Ok(Json(serde_json::json!(data)))
+    let Some(found) = cities::lookup(&city) else {
+        return Ok(unknown_city(&city));
+    };
+    let data = weather::get_weather(found);
+    Ok(Json(weather::with_units(&data, query.units)).into_response())
+}
+
+/// Alerts for the city's current conditions and forecast, as found by the
+/// background evaluation that also publishes them as `weather.alert` events.
+pub async fn get_alerts(
+    State(state): State<AppState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    Path(city): Path<String>,
+) -> Result<Response, StatusCode> {
+    state
+        .rate_limiter
+        .check_rate_limit(&addr.ip().to_string(), "weather/alerts")?;
+
+    let Some(found) = cities::lookup(&city) else {
+        return Ok(unknown_city(&city));
+    };
+    let thresholds = state.alert_rules.for_city(found);
+    let found_alerts = state
+        .weather_alerts
+        .read()
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .get(&found.id)
+        .cloned()
+        .unwrap_or_default();
+
+    Ok(Json(serde_json::json!({
+        "city": found.name,
+        "thresholds": thresholds,
+        "count": found_alerts.len(),
+        "alerts": found_alerts,
+    }))
+    .into_response())
+}
+
+/// Query parameters for `/api/weather/{city}/history`.
+///
+/// `from` and `to` are `YYYY-MM-DD` dates in the city's time zone.
+#[derive(Debug, Deserialize)]
+pub struct HistoryQuery {
+    pub from: Option<String>,
+    pub to: Option<String>,
+}
+
+/// Days of history returned when `from` is not given.
+const DEFAULT_HISTORY_DAYS: i64 = 30;
+
+/// Recorded daily observations between `from` and `to`, each with its
+/// deviation from the month's normal, plus the normals and how today
+/// compares. Days appear once the weather collector (scraper-005) has run.
+pub async fn get_history(
+    State(state): State<AppState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    Path(city): Path<String>,
+    Query(query): Query<HistoryQuery>,
+) -> Result<Response, StatusCode> {
+    state
+        .rate_limiter
+        .check_rate_limit(&addr.ip().to_string(), "weather/history")?;
+
+    let Some(found) = cities::lookup(&city) else {
+        return Ok(unknown_city(&city));
+    };
+    let parse = |raw: &str| NaiveDate::parse_from_str(raw, "%Y-%m-%d").ok();
+    let today = Utc::now().with_timezone(&found.tz()).date_naive();
+    let to = match query.to.as_deref() {
+        Some(raw) => parse(raw).ok_or(StatusCode::BAD_REQUEST)?,
+        None => today,
+    };
+    let from = match query.from.as_deref() {
+        Some(raw) => parse(raw).ok_or(StatusCode::BAD_REQUEST)?,
+        None => to - TimeDelta::days(DEFAULT_HISTORY_DAYS - 1),
+    };
+    if from > to {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let normals = weather::normals(found);
+    let normal = |date: NaiveDate| &normals[date.month0() as usize];
+    let midnight = |date: NaiveDate| date.and_time(Default::default()).and_utc();
+    let store = Arc::clone(&state.store);
+    let (id, first, last) = (
+        found.id.clone(),
+        midnight(from.min(today)),
+        midnight(to.max(today)),
+    );
+    let recorded =
+        tokio::task::spawn_blocking(move || store.query(&records::WEATHER, Some(&id), first, last))
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let observations: Vec<serde_json::Value> = recorded
+        .iter()
+        .map(|record| &record.observation)
+        .filter(|day| day.date >= from && day.date <= to)
+        .map(|day| {
+            serde_json::json!({
+                "observation": day,
+                "deviation": day.deviation(normal(day.date)),
+            })
+        })
+        .collect();
+    let today_summary = recorded
+        .iter()
+        .map(|record| &record.observation)
+        .find(|day| day.date == today)
+        .map(|day| {
+            serde_json::json!({
+                "observation": day,
+                "normal": normal(today),
+                "deviation": day.deviation(normal(today)),
+            })
+        });
+
+    Ok(Json(serde_json::json!({
+        "city": found.name,
+        "from": from,
+        "to": to,
+        "count": observations.len(),
+        "observations": observations,
+        "normals": normals,
+        "today": today_summary,
+    }))
+    .into_response())
+}
+
+/// `404 Not Found` naming the closest known cities.
+fn unknown_city(query: &str) -> Response {
+    let suggestions = cities::suggestions(query);
+    (
+        StatusCode::NOT_FOUND,
+        Json(serde_json::json!({
+            "error": format!("unknown city: {query}"),
+            "suggestions": suggestions,
+        })),
+    )
+        .into_response()
+}
+
+/// Query parameters for `/api/weather/cities`.
+#[derive(Debug, Deserialize)]
+pub struct CitiesQuery {
+    pub q: Option<String>,
+    /// Country name or ISO code.
+    pub country: Option<String>,
+}
+
+pub async fn get_cities(
+    State(state): State<AppState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    Query(query): Query<CitiesQuery>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    state
+        .rate_limiter
+        .check_rate_limit(&addr.ip().to_string(), "weather/cities")?;
+
+    let found = cities::search(query.q.as_deref(), query.country.as_deref());
+    Ok(Json(serde_json::json!({
+        "count": found.len(),
+        "cities": found,
+    })))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::to_bytes;
+    use axum::http::Uri;
+
+    fn client() -> ConnectInfo<SocketAddr> {
+        ConnectInfo(([127, 0, 0, 1], 4000).into())
+    }
+
+    fn query<T: serde::de::DeserializeOwned>(raw: &str) -> Query<T> {
+        let uri: Uri = format!("/api/weather/tokyo?{raw}").parse().unwrap();
+        Query::try_from_uri(&uri).unwrap()
+    }
+
+    async fn body(response: Response) -> (StatusCode, serde_json::Value) {
+        let status = response.status();
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&bytes).unwrap())
+    }
+
+    #[tokio::test]
+    async fn unknown_cities_are_404_with_suggestions() {
+        let state = AppState::scratch("weather-unknown");
+        let response = get_weather(
+            State(state.clone()),
+            client(),
+            Path("tokio".to_string()),
+            query(""),
+        )
+        .await
+        .unwrap();
+        let (status, json) = body(response).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(json["error"], "unknown city: tokio");
+        assert_eq!(json["suggestions"][0]["name"], "Tokyo");
+
+        let history = get_history(
+            State(state),
+            client(),
+            Path("atlantis".to_string()),
+            query(""),
+        )
+        .await
+        .unwrap();
+        assert_eq!(history.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn units_are_validated_and_converted() {
+        let uri: Uri = "/api/weather/tokyo?units=kelvin".parse().unwrap();
+        let rejection = Query::<WeatherQuery>::try_from_uri(&uri).unwrap_err();
+        assert_eq!(rejection.into_response().status(), StatusCode::BAD_REQUEST);
+
+        let state = AppState::scratch("weather-units");
+        let fetch = |units: &str| {
+            get_weather(
+                State(state.clone()),
+                client(),
+                Path("tokyo".to_string()),
+                query(&format!("units={units}")),
+            )
+        };
+        let (_, metric) = body(fetch("metric").await.unwrap()).await;
+        let (status, imperial) = body(fetch("imperial").await.unwrap()).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(imperial["units"]["system"], "imperial");
+        assert!(imperial["current"].get("temperature_c").is_none());
+        let celsius = metric["current"]["temperature_c"].as_f64().unwrap();
+        let fahrenheit = imperial["current"]["temperature_f"].as_f64().unwrap();
+        assert!((fahrenheit - (celsius * 9.0 / 5.0 + 32.0)).abs() <= 0.1);
+    }
+
+    #[tokio::test]
+    async fn history_validates_its_window() {
+        let state = AppState::scratch("weather-history");
+        let tokyo = cities::lookup("tokyo").unwrap();
+        records::record_weather(&state.store, tokyo, Utc::now()).unwrap();
+        let fetch = |raw: &str| {
+            get_history(
+                State(state.clone()),
+                client(),
+                Path("tokyo".to_string()),
+                query(raw),
+            )
+        };
+
+        for raw in [
+            "from=2026-13-01",
+            "to=yesterday",
+            "from=2026-03-02&to=2026-03-01",
+        ] {
+            assert_eq!(
+                fetch(raw).await.unwrap_err(),
+                StatusCode::BAD_REQUEST,
+                "{raw}"
+            );
+        }
+        let (status, json) = body(fetch("").await.unwrap()).await;
+        assert_eq!(status, StatusCode::OK);
+        let today = Utc::now().with_timezone(&tokyo.tz()).date_naive();
+        assert_eq!(json["to"], today.to_string());
+        assert_eq!(
+            json["from"],
+            (today - TimeDelta::days(DEFAULT_HISTORY_DAYS - 1)).to_string()
+        );
+        assert_eq!(json["count"], 1);
+        assert_eq!(json["today"]["observation"]["date"], today.to_string());
+    }
 }
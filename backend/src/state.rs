@@ -1,9 +1,61 @@
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
 
+use crate::analysis::alerts::{Alert, AlertRules};
+use crate::config::{AdminConfig, SourceConfig};
+use crate::events::EventBus;
+use crate::mock_data::news::NewsArticle;
+use crate::mock_data::social::SocialPost;
 use crate::rate_limiter::RateLimiter;
+use crate::storage::records::Maintenance;
+use crate::storage::Store;
 
 /// Shared application state accessible from all route handlers.
 #[derive(Clone)]
 pub struct AppState {
     pub rate_limiter: Arc<RateLimiter>,
+    pub sources: Arc<SourceConfig>,
+    pub admin: Arc<AdminConfig>,
+    /// Posts from the latest social ingestion, oldest first.
+    pub social_posts: Arc<RwLock<Vec<SocialPost>>>,
+    /// The served news feed: the mock articles plus every stored article
+    /// the news aggregator has ingested.
+    pub news_articles: Arc<RwLock<Vec<NewsArticle>>>,
+    /// Weather alert thresholds, bundled or from `ALERT_THRESHOLDS`.
+    pub alert_rules: Arc<AlertRules>,
+    /// Alerts per city id from the latest background evaluation.
+    pub weather_alerts: Arc<RwLock<HashMap<String, Vec<Alert>>>>,
+    pub events: Arc<EventBus>,
+    /// Persisted time series: prices, sentiment, weather and articles.
+    pub store: Arc<Store>,
+    /// The latest background compaction and retention pass.
+    pub maintenance: Arc<RwLock<Option<Maintenance>>>,
+}
+
+#[cfg(test)]
+impl AppState {
+    /// State for handler tests: an empty store under the temp directory
+    /// named after the test, no admin token and generous rate limits.
+    pub fn scratch(name: &str) -> Self {
+        use crate::config::RateLimitConfig;
+
+        let root = std::env::temp_dir().join(format!("datapulse-{}-{name}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        Self {
+            rate_limiter: Arc::new(RateLimiter::new(RateLimitConfig {
+                global_daily_limit: 1000,
+                endpoint_daily_limit: 1000,
+                endpoint_minute_limit: 1000,
+            })),
+            sources: Arc::new(SourceConfig::from_env()),
+            admin: Arc::new(AdminConfig { token: None }),
+            social_posts: Arc::new(RwLock::new(Vec::new())),
+            news_articles: Arc::new(RwLock::new(Vec::new())),
+            alert_rules: Arc::new(AlertRules::bundled().clone()),
+            weather_alerts: Arc::new(RwLock::new(HashMap::new())),
+            events: Arc::new(EventBus::new(Vec::new())),
+            store: Arc::new(Store::open(root).unwrap()),
+            maintenance: Arc::new(RwLock::new(None)),
+        }
+    }
 }
@@ -0,0 +1,563 @@
+pub mod records;
+
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use chrono::{DateTime, DurationRound, NaiveDate, TimeDelta, Utc};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+const SEGMENT_EXTENSION: &str = "ndjson";
+
+/// How long after a bucket ends before it is rolled up, so records stamped
+/// just before the boundary have time to be appended.
+const ROLL_UP_GRACE: TimeDelta = TimeDelta::seconds(5);
+
+#[derive(Debug)]
+pub enum StoreError {
+    Io(std::io::Error),
+    Encode(serde_json::Error),
+}
+
+impl fmt::Display for StoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "storage I/O failed: {err}"),
+            Self::Encode(err) => write!(f, "could not encode record: {err}"),
+        }
+    }
+}
+
+impl From<std::io::Error> for StoreError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+/// A time-stamped record that can be kept in a series.
+pub trait Record: Serialize + DeserializeOwned {
+    /// What the record describes within its series, e.g. a coin symbol or a
+    /// city id. A record with the same key and timestamp as an earlier one
+    /// replaces it.
+    fn key(&self) -> &str;
+    fn timestamp(&self) -> DateTime<Utc>;
+}
+
+/// A record that can be combined with others of its key into one record
+/// covering a longer interval.
+pub trait Downsample: Record + Sized {
+    /// Merge `records`, all with `key` and within the bucket starting at
+    /// `start`, into one record stamped `start`.
+    fn merge(key: &str, start: DateTime<Utc>, records: &[Self]) -> Self;
+}
+
+/// How long a series keeps its records.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Retention {
+    Forever,
+    Days(i64),
+}
+
+/// A named series of `R` records; see `records` for the ones in use.
+pub struct Series<R> {
+    name: &'static str,
+    retention: Retention,
+    record: PhantomData<fn() -> R>,
+}
+
+impl<R: Record> Series<R> {
+    pub const fn new(name: &'static str, retention: Retention) -> Self {
+        Self {
+            name,
+            retention,
+            record: PhantomData,
+        }
+    }
+}
+
+/// Disk used by one series.
+#[derive(Debug, Clone, Serialize)]
+pub struct SeriesUsage {
+    pub series: &'static str,
+    pub retention: Retention,
+    pub segments: usize,
+    pub bytes: u64,
+    /// Days of the oldest and newest segments.
+    pub oldest: Option<NaiveDate>,
+    pub newest: Option<NaiveDate>,
+}
+
+/// Embedded storage for time-stamped records, rooted at one directory.
+///
+/// Each series is a directory of append-only NDJSON segments, one per UTC
+/// day: `<root>/<series>/<YYYY-MM-DD>.ndjson`. Appends are synced before
+/// returning and serialized so lines never interleave; a line torn by a
+/// crash is skipped on read. Retention drops whole segments, so nothing is
+/// rewritten in place.
+pub struct Store {
+    root: PathBuf,
+    write_lock: Mutex<()>,
+}
+
+impl Store {
+    /// Open the store at `root`, creating the directory if needed.
+    pub fn open(root: impl Into<PathBuf>) -> Result<Self, StoreError> {
+        let root = root.into();
+        fs::create_dir_all(&root)?;
+        Ok(Self {
+            root,
+            write_lock: Mutex::new(()),
+        })
+    }
+
+    /// Append `records` to the segments for their days.
+    pub fn append<R: Record>(&self, series: &Series<R>, records: &[R]) -> Result<(), StoreError> {
+        let _guard = self.write_lock.lock().unwrap_or_else(|e| e.into_inner());
+        self.write(series, records)
+    }
+
+    /// Append only the `records` whose key and timestamp are not stored yet,
+    /// nor repeated earlier in `records`, returning how many were written.
+    pub fn append_new<R: Record + Clone>(
+        &self,
+        series: &Series<R>,
+        records: &[R],
+    ) -> Result<usize, StoreError> {
+        let _guard = self.write_lock.lock().unwrap_or_else(|e| e.into_inner());
+        let days: BTreeSet<NaiveDate> =
+            records.iter().map(|r| r.timestamp().date_naive()).collect();
+        let mut seen = HashSet::new();
+        for day in days {
+            for stored in self.read_segment::<R>(series.name, day)? {
+                seen.insert((stored.timestamp(), stored.key().to_string()));
+            }
+        }
+        let fresh: Vec<R> = records
+            .iter()
+            .filter(|r| seen.insert((r.timestamp(), r.key().to_string())))
+            .cloned()
+            .collect();
+        self.write(series, &fresh)?;
+        Ok(fresh.len())
+    }
+
+    /// Append `records` to their segments; callers hold the write lock.
+    fn write<R: Record>(&self, series: &Series<R>, records: &[R]) -> Result<(), StoreError> {
+        let mut by_day: BTreeMap<NaiveDate, String> = BTreeMap::new();
+        for record in records {
+            let line = serde_json::to_string(record).map_err(StoreError::Encode)?;
+            let segment = by_day.entry(record.timestamp().date_naive()).or_default();
+            segment.push_str(&line);
+            segment.push('\n');
+        }
+
+        let dir = self.root.join(series.name);
+        fs::create_dir_all(&dir)?;
+        for (day, lines) in by_day {
+            let mut file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(segment_path(&dir, day))?;
+            file.write_all(lines.as_bytes())?;
+            file.sync_data()?;
+        }
+        Ok(())
+    }
+
+    /// Records stamped within `from..=to`, optionally only those for `key`,
+    /// oldest first (ties by key).
+    pub fn query<R: Record>(
+        &self,
+        series: &Series<R>,
+        key: Option<&str>,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<R>, StoreError> {
+        let mut found: BTreeMap<(DateTime<Utc>, String), R> = BTreeMap::new();
+        for day in self.days(series.name)? {
+            if day < from.date_naive() || day > to.date_naive() {
+                continue;
+            }
+            for record in self.read_segment::<R>(series.name, day)? {
+                let timestamp = record.timestamp();
+                if timestamp >= from && timestamp <= to && key.is_none_or(|key| record.key() == key)
+                {
+                    found.insert((timestamp, record.key().to_string()), record);
+                }
+            }
+        }
+        Ok(found.into_values().collect())
+    }
+
+    /// The newest record, optionally only among those for `key`.
+    pub fn latest<R: Record>(
+        &self,
+        series: &Series<R>,
+        key: Option<&str>,
+    ) -> Result<Option<R>, StoreError> {
+        for day in self.days(series.name)?.into_iter().rev() {
+            let newest = self
+                .read_segment::<R>(series.name, day)?
+                .into_iter()
+                .filter(|record| key.is_none_or(|key| record.key() == key))
+                .reduce(|newest, record| {
+                    if record.timestamp() >= newest.timestamp() {
+                        record
+                    } else {
+                        newest
+                    }
+                });
+            if newest.is_some() {
+                return Ok(newest);
+            }
+        }
+        Ok(None)
+    }
+
+    /// Merge `source` records into `bucket`-long records in `target` for
+    /// every bucket that has ended by `now` and comes after the newest one
+    /// already in `target`, returning how many were written.
+    pub fn roll_up<R, B>(
+        &self,
+        source: &Series<R>,
+        target: &Series<B>,
+        bucket: TimeDelta,
+        now: DateTime<Utc>,
+    ) -> Result<usize, StoreError>
+    where
+        R: Record,
+        B: Downsample + From<R>,
+    {
+        let end = (now - ROLL_UP_GRACE).duration_trunc(bucket).unwrap_or(now);
+        let start = self
+            .latest(target, None)?
+            .map_or(DateTime::UNIX_EPOCH, |last| last.timestamp() + bucket);
+        if start >= end {
+            return Ok(0);
+        }
+        let records = self.query(source, None, start, end - TimeDelta::nanoseconds(1))?;
+        let rolled = downsample(records.into_iter().map(B::from).collect(), bucket);
+        self.append(target, &rolled)?;
+        Ok(rolled.len())
+    }
+
+    /// Segments and bytes on disk for `series`, from file metadata alone.
+    pub fn usage<R: Record>(&self, series: &Series<R>) -> Result<SeriesUsage, StoreError> {
+        let days = self.days(series.name)?;
+        let dir = self.root.join(series.name);
+        let mut bytes = 0;
+        for &day in &days {
+            bytes += fs::metadata(segment_path(&dir, day))?.len();
+        }
+        Ok(SeriesUsage {
+            series: series.name,
+            retention: series.retention,
+            segments: days.len(),
+            bytes,
+            oldest: days.first().copied(),
+            newest: days.last().copied(),
+        })
+    }
+
+    /// Delete the segments of `series` that lie wholly before its retention
+    /// window ending at `now`, returning how many were removed.
+    pub fn enforce_retention<R: Record>(
+        &self,
+        series: &Series<R>,
+        now: DateTime<Utc>,
+    ) -> Result<usize, StoreError> {
+        let Retention::Days(days) = series.retention else {
+            return Ok(0);
+        };
+        let cutoff = (now - TimeDelta::days(days)).date_naive();
+        let dir = self.root.join(series.name);
+        let _guard = self.write_lock.lock().unwrap_or_else(|e| e.into_inner());
+        let mut removed = 0;
+        for day in self.days(series.name)? {
+            if day < cutoff {
+                fs::remove_file(segment_path(&dir, day))?;
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    }
+
+    /// Days that have a segment in `series`, oldest first.
+    fn days(&self, series: &str) -> Result<Vec<NaiveDate>, StoreError> {
+        let entries = match fs::read_dir(self.root.join(series)) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err.into()),
+        };
+        let mut days = Vec::new();
+        for entry in entries {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == SEGMENT_EXTENSION) {
+                if let Some(day) = path
+                    .file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .and_then(|stem| NaiveDate::parse_from_str(stem, "%Y-%m-%d").ok())
+                {
+                    days.push(day);
+                }
+            }
+        }
+        days.sort();
+        Ok(days)
+    }
+
+    /// Every readable record in one segment, in append order.
+    fn read_segment<R: Record>(&self, series: &str, day: NaiveDate) -> Result<Vec<R>, StoreError> {
+        let text = match fs::read_to_string(segment_path(&self.root.join(series), day)) {
+            Ok(text) => text,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err.into()),
+        };
+        Ok(text
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect())
+    }
+}
+
+fn segment_path(dir: &Path, day: NaiveDate) -> PathBuf {
+    dir.join(format!("{}.{SEGMENT_EXTENSION}", day.format("%Y-%m-%d")))
+}
+
+/// Merge `records` into one per key and `bucket`-long interval, with buckets
+/// aligned to the Unix epoch. Returned oldest first (ties by key).
+pub fn downsample<R: Downsample>(records: Vec<R>, bucket: TimeDelta) -> Vec<R> {
+    let mut buckets: BTreeMap<(DateTime<Utc>, String), Vec<R>> = BTreeMap::new();
+    for record in records {
+        let timestamp = record.timestamp();
+        let start = timestamp.duration_trunc(bucket).unwrap_or(timestamp);
+        buckets
+            .entry((start, record.key().to_string()))
+            .or_default()
+            .push(record);
+    }
+    buckets
+        .into_iter()
+        .map(|((start, key), records)| R::merge(&key, start, &records))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Reading {
+        sensor: String,
+        at: DateTime<Utc>,
+        value: f64,
+    }
+
+    impl Record for Reading {
+        fn key(&self) -> &str {
+            &self.sensor
+        }
+
+        fn timestamp(&self) -> DateTime<Utc> {
+            self.at
+        }
+    }
+
+    impl Downsample for Reading {
+        fn merge(key: &str, start: DateTime<Utc>, records: &[Self]) -> Self {
+            Reading {
+                sensor: key.to_string(),
+                at: start,
+                value: records.iter().map(|r| r.value).sum::<f64>() / records.len() as f64,
+            }
+        }
+    }
+
+    const READINGS: Series<Reading> = Series::new("readings", Retention::Days(2));
+
+    fn reading(sensor: &str, at: &str, value: f64) -> Reading {
+        Reading {
+            sensor: sensor.to_string(),
+            at: at.parse().unwrap(),
+            value,
+        }
+    }
+
+    fn scratch(name: &str) -> Store {
+        let root = std::env::temp_dir().join(format!("datapulse-{}-{name}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        Store::open(root).unwrap()
+    }
+
+    #[test]
+    fn appends_and_queries_across_segments() {
+        let store = scratch("query");
+        store
+            .append(
+                &READINGS,
+                &[
+                    reading("a", "2026-03-01T23:00:00Z", 1.0),
+                    reading("b", "2026-03-02T01:00:00Z", 2.0),
+                    reading("a", "2026-03-02T02:00:00Z", 3.0),
+                ],
+            )
+            .unwrap();
+        // Same key and time as an earlier record: replaces it.
+        store
+            .append(&READINGS, &[reading("a", "2026-03-02T02:00:00Z", 4.0)])
+            .unwrap();
+        // A torn final line is skipped.
+        let segment = store.root.join("readings/2026-03-02.ndjson");
+        let mut file = OpenOptions::new().append(true).open(segment).unwrap();
+        file.write_all(b"{\"sensor\":\"a\",\"at\"").unwrap();
+
+        let all = |key| {
+            store
+                .query(
+                    &READINGS,
+                    key,
+                    "2026-03-01T00:00:00Z".parse().unwrap(),
+                    "2026-03-03T00:00:00Z".parse().unwrap(),
+                )
+                .unwrap()
+        };
+        let values: Vec<f64> = all(None).iter().map(|r| r.value).collect();
+        assert_eq!(values, vec![1.0, 2.0, 4.0]);
+        assert_eq!(
+            all(Some("b")),
+            vec![reading("b", "2026-03-02T01:00:00Z", 2.0)]
+        );
+        assert_eq!(
+            store.latest(&READINGS, Some("a")).unwrap().unwrap().value,
+            4.0
+        );
+        assert_eq!(store.latest(&READINGS, None).unwrap().unwrap().value, 4.0);
+        assert!(store.latest(&READINGS, Some("z")).unwrap().is_none());
+
+        let usage = store.usage(&READINGS).unwrap();
+        assert_eq!(usage.segments, 2);
+        assert!(usage.bytes > 0);
+        assert_eq!(usage.newest, NaiveDate::from_ymd_opt(2026, 3, 2));
+    }
+
+    #[test]
+    fn appends_only_records_not_yet_stored() {
+        let store = scratch("append-new");
+        let first = [
+            reading("a", "2026-03-01T10:00:00Z", 1.0),
+            reading("b", "2026-03-01T10:00:00Z", 2.0),
+        ];
+        assert_eq!(store.append_new(&READINGS, &first).unwrap(), 2);
+        let again = [
+            reading("a", "2026-03-01T10:00:00Z", 5.0),
+            reading("a", "2026-03-02T10:00:00Z", 3.0),
+            reading("a", "2026-03-02T10:00:00Z", 4.0),
+        ];
+        assert_eq!(store.append_new(&READINGS, &again).unwrap(), 1);
+        let lines: usize = ["2026-03-01", "2026-03-02"]
+            .iter()
+            .map(|day| {
+                fs::read_to_string(store.root.join(format!("readings/{day}.ndjson")))
+                    .unwrap()
+                    .lines()
+                    .count()
+            })
+            .sum();
+        assert_eq!(lines, 3);
+        assert_eq!(
+            store.latest(&READINGS, Some("a")).unwrap().unwrap().value,
+            3.0
+        );
+    }
+
+    #[test]
+    fn retention_drops_whole_old_segments() {
+        let store = scratch("retention");
+        store
+            .append(
+                &READINGS,
+                &[
+                    reading("a", "2026-03-01T12:00:00Z", 1.0),
+                    reading("a", "2026-03-03T12:00:00Z", 2.0),
+                ],
+            )
+            .unwrap();
+        let now = "2026-03-04T06:00:00Z".parse().unwrap();
+        assert_eq!(store.enforce_retention(&READINGS, now).unwrap(), 1);
+        assert_eq!(store.days("readings").unwrap().len(), 1);
+        assert_eq!(store.enforce_retention(&READINGS, now).unwrap(), 0);
+    }
+
+    #[test]
+    fn rolls_up_finished_buckets_once() {
+        const HOURLY: Series<Reading> = Series::new("hourly", Retention::Forever);
+        let store = scratch("roll-up");
+        store
+            .append(
+                &READINGS,
+                &[
+                    reading("a", "2026-03-01T10:10:00Z", 1.0),
+                    reading("a", "2026-03-01T10:50:00Z", 3.0),
+                    reading("a", "2026-03-01T11:20:00Z", 5.0),
+                ],
+            )
+            .unwrap();
+        let roll_up = |now: &str| {
+            store
+                .roll_up(
+                    &READINGS,
+                    &HOURLY,
+                    TimeDelta::hours(1),
+                    now.parse().unwrap(),
+                )
+                .unwrap()
+        };
+        // 11:00 has not finished, and 10:00 is not rolled up twice.
+        assert_eq!(roll_up("2026-03-01T11:30:00Z"), 1);
+        assert_eq!(roll_up("2026-03-01T11:59:59Z"), 0);
+        assert_eq!(roll_up("2026-03-01T12:00:10Z"), 1);
+        let all = store
+            .query(
+                &HOURLY,
+                None,
+                DateTime::UNIX_EPOCH,
+                "2026-03-02T00:00:00Z".parse().unwrap(),
+            )
+            .unwrap();
+        assert_eq!(
+            all,
+            vec![
+                reading("a", "2026-03-01T10:00:00Z", 2.0),
+                reading("a", "2026-03-01T11:00:00Z", 5.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn downsamples_per_key_and_bucket() {
+        let merged = downsample(
+            vec![
+                reading("a", "2026-03-01T10:05:00Z", 1.0),
+                reading("a", "2026-03-01T10:55:00Z", 3.0),
+                reading("b", "2026-03-01T10:30:00Z", 5.0),
+                reading("a", "2026-03-01T11:00:00Z", 7.0),
+            ],
+            TimeDelta::hours(1),
+        );
+        assert_eq!(
+            merged,
+            vec![
+                reading("a", "2026-03-01T10:00:00Z", 2.0),
+                reading("b", "2026-03-01T10:00:00Z", 5.0),
+                reading("a", "2026-03-01T11:00:00Z", 7.0),
+            ]
+        );
+    }
+}
@@ -0,0 +1,421 @@
+use chrono::{DateTime, TimeDelta, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{downsample, Downsample, Record, Retention, Series, SeriesUsage, Store, StoreError};
+use crate::mock_data::cities::City;
+use crate::mock_data::crypto;
+use crate::mock_data::news::NewsArticle;
+use crate::mock_data::weather::{self, Observation};
+
+/// Coin price ticks recorded by the price collector.
+pub const PRICES: Series<PricePoint> = Series::new("prices", Retention::Days(7));
+
+/// Price ticks compacted into one-minute bars.
+pub const PRICE_BARS_1M: Series<PriceBar> = Series::new("prices_1m", Retention::Days(90));
+
+/// One-minute bars compacted into daily bars.
+pub const PRICE_BARS_1D: Series<PriceBar> = Series::new("prices_1d", Retention::Forever);
+
+/// Hourly sentiment per hashtag from social ingestion.
+pub const SENTIMENT: Series<SentimentPoint> = Series::new("sentiment", Retention::Days(90));
+
+/// Prices read off saved product pages by the price tracker.
+pub const PRODUCT_PRICES: Series<ProductPrice> =
+    Series::new("product_prices", Retention::Days(365));
+
+/// Daily observations recorded by the weather collector.
+pub const WEATHER: Series<WeatherObservation> = Series::new("weather", Retention::Forever);
+
+/// Articles ingested by the news aggregator.
+pub const ARTICLES: Series<NewsArticle> = Series::new("articles", Retention::Days(365));
+
+/// What one maintenance pass did.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct Maintenance {
+    pub ran_at: DateTime<Utc>,
+    /// Bars written by compaction.
+    pub compacted: usize,
+    /// Segments dropped by retention.
+    pub pruned: usize,
+}
+
+/// Compact raw prices into bars, then apply every series' retention policy.
+/// Compaction runs first so ticks are rolled up before they expire.
+pub fn maintain(store: &Store, now: DateTime<Utc>) -> Result<Maintenance, StoreError> {
+    let compacted = store.roll_up(&PRICES, &PRICE_BARS_1M, TimeDelta::minutes(1), now)?
+        + store.roll_up(&PRICE_BARS_1M, &PRICE_BARS_1D, TimeDelta::days(1), now)?;
+    let pruned = store.enforce_retention(&PRICES, now)?
+        + store.enforce_retention(&PRICE_BARS_1M, now)?
+        + store.enforce_retention(&PRICE_BARS_1D, now)?
+        + store.enforce_retention(&PRODUCT_PRICES, now)?
+        + store.enforce_retention(&SENTIMENT, now)?
+        + store.enforce_retention(&WEATHER, now)?
+        + store.enforce_retention(&ARTICLES, now)?;
+    Ok(Maintenance {
+        ran_at: now,
+        compacted,
+        pruned,
+    })
+}
+
+/// Record one tick of every coin's current price, returning how many.
+pub fn record_prices(store: &Store, now: DateTime<Utc>) -> Result<usize, StoreError> {
+    let ticks: Vec<PricePoint> = crypto::get_prices()
+        .into_iter()
+        .map(|coin| PricePoint {
+            symbol: coin.symbol,
+            timestamp: now,
+            price: coin.current_price,
+            volume_24h: coin.volume_24h,
+        })
+        .collect();
+    store.append(&PRICES, &ticks)?;
+    Ok(ticks.len())
+}
+
+/// Record `city`'s days from its last recorded one through today, returning
+/// how many were written. The last recorded day is observed again, so a day
+/// recorded while still in progress is completed on the next run; it is only
+/// written again if it changed. A city's first record is today: nothing
+/// before it was observed.
+pub fn record_weather(store: &Store, city: &City, now: DateTime<Utc>) -> Result<usize, StoreError> {
+    let today = now.with_timezone(&city.tz()).date_naive();
+    let last = store
+        .latest(&WEATHER, Some(&city.id))?
+        .map(|last| last.observation);
+    let mut date = last.as_ref().map_or(today, |last| last.date);
+    let mut observations = Vec::new();
+    while date <= today {
+        if let Some(observation) = weather::observe(city, date, now) {
+            if last.as_ref() != Some(&observation) {
+                observations.push(WeatherObservation {
+                    city: city.id.clone(),
+                    observation,
+                });
+            }
+        }
+        date += TimeDelta::days(1);
+    }
+    store.append(&WEATHER, &observations)?;
+    Ok(observations.len())
+}
+
+/// Disk usage of every series.
+pub fn usage(store: &Store) -> Result<Vec<SeriesUsage>, StoreError> {
+    Ok(vec![
+        store.usage(&PRICES)?,
+        store.usage(&PRICE_BARS_1M)?,
+        store.usage(&PRICE_BARS_1D)?,
+        store.usage(&PRODUCT_PRICES)?,
+        store.usage(&SENTIMENT)?,
+        store.usage(&WEATHER)?,
+        store.usage(&ARTICLES)?,
+    ])
+}
+
+/// Bars of `bucket` length for `symbol` over `from..=to`. Daily bars come
+/// from their own series; shorter ones from the one-minute bars. Whatever
+/// has not been compacted yet is rolled up from the finer data on the fly.
+pub fn price_bars(
+    store: &Store,
+    symbol: &str,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    bucket: TimeDelta,
+) -> Result<Vec<PriceBar>, StoreError> {
+    let top_up = |mut bars: Vec<PriceBar>, finer: Vec<PriceBar>, bucket: TimeDelta| {
+        let after = bars.last().map_or(from, |last| last.timestamp + bucket);
+        let rest = finer
+            .into_iter()
+            .filter(|bar| bar.timestamp >= after)
+            .collect();
+        bars.extend(downsample(rest, bucket));
+        bars
+    };
+    let minute = store.query(&PRICE_BARS_1M, Some(symbol), from, to)?;
+    let after = minute
+        .last()
+        .map_or(from, |last| last.timestamp + TimeDelta::minutes(1));
+    let ticks = store
+        .query(&PRICES, Some(symbol), after, to)?
+        .into_iter()
+        .map(PriceBar::from)
+        .collect();
+    let minute = top_up(minute, ticks, TimeDelta::minutes(1));
+
+    if bucket == TimeDelta::days(1) {
+        let daily = store.query(&PRICE_BARS_1D, Some(symbol), from, to)?;
+        Ok(top_up(daily, minute, bucket))
+    } else {
+        Ok(downsample(minute, bucket))
+    }
+}
+
+/// A coin's price at one moment.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PricePoint {
+    pub symbol: String,
+    pub timestamp: DateTime<Utc>,
+    pub price: f64,
+    pub volume_24h: u64,
+}
+
+impl Record for PricePoint {
+    fn key(&self) -> &str {
+        &self.symbol
+    }
+
+    fn timestamp(&self) -> DateTime<Utc> {
+        self.timestamp
+    }
+}
+
+/// A coin's opening, highest, lowest and closing price over the interval
+/// starting at `timestamp`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PriceBar {
+    pub symbol: String,
+    pub timestamp: DateTime<Utc>,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    /// The latest 24-hour volume in the interval.
+    pub volume_24h: u64,
+    /// Ticks the bar was built from.
+    pub ticks: u64,
+}
+
+impl From<PricePoint> for PriceBar {
+    /// A bar covering a single tick.
+    fn from(point: PricePoint) -> Self {
+        Self {
+            symbol: point.symbol,
+            timestamp: point.timestamp,
+            open: point.price,
+            high: point.price,
+            low: point.price,
+            close: point.price,
+            volume_24h: point.volume_24h,
+            ticks: 1,
+        }
+    }
+}
+
+impl Record for PriceBar {
+    fn key(&self) -> &str {
+        &self.symbol
+    }
+
+    fn timestamp(&self) -> DateTime<Utc> {
+        self.timestamp
+    }
+}
+
+impl Downsample for PriceBar {
+    fn merge(key: &str, start: DateTime<Utc>, records: &[Self]) -> Self {
+        let first = records.iter().min_by_key(|bar| bar.timestamp);
+        let last = records.iter().max_by_key(|bar| bar.timestamp);
+        Self {
+            symbol: key.to_string(),
+            timestamp: start,
+            open: first.map_or(0.0, |bar| bar.open),
+            high: records.iter().map(|bar| bar.high).fold(f64::MIN, f64::max),
+            low: records.iter().map(|bar| bar.low).fold(f64::MAX, f64::min),
+            close: last.map_or(0.0, |bar| bar.close),
+            volume_24h: last.map_or(0, |bar| bar.volume_24h),
+            ticks: records.iter().map(|bar| bar.ticks).sum(),
+        }
+    }
+}
+
+/// A product's price on one retailer's page when the price tracker ran.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProductPrice {
+    pub product_id: String,
+    pub source: String,
+    pub timestamp: DateTime<Utc>,
+    pub price: f64,
+    pub currency: String,
+    pub in_stock: bool,
+}
+
+impl Record for ProductPrice {
+    fn key(&self) -> &str {
+        &self.product_id
+    }
+
+    fn timestamp(&self) -> DateTime<Utc> {
+        self.timestamp
+    }
+}
+
+/// Shares of positive, negative and neutral posts for a hashtag over the
+/// hour starting at `timestamp`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SentimentPoint {
+    pub hashtag: String,
+    pub timestamp: DateTime<Utc>,
+    pub positive: f64,
+    pub negative: f64,
+    pub neutral: f64,
+    pub mentions: u64,
+}
+
+impl Record for SentimentPoint {
+    fn key(&self) -> &str {
+        &self.hashtag
+    }
+
+    fn timestamp(&self) -> DateTime<Utc> {
+        self.timestamp
+    }
+}
+
+impl Downsample for SentimentPoint {
+    /// Shares weighted by mentions; mentions summed.
+    fn merge(key: &str, start: DateTime<Utc>, records: &[Self]) -> Self {
+        let mentions: u64 = records.iter().map(|r| r.mentions).sum();
+        let share = |value: fn(&Self) -> f64| {
+            let weighted: f64 = records.iter().map(|r| value(r) * r.mentions as f64).sum();
+            (weighted / mentions.max(1) as f64 * 10.0).round() / 10.0
+        };
+        Self {
+            hashtag: key.to_string(),
+            timestamp: start,
+            positive: share(|r| r.positive),
+            negative: share(|r| r.negative),
+            neutral: share(|r| r.neutral),
+            mentions,
+        }
+    }
+}
+
+/// One city's observed day.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WeatherObservation {
+    /// City id from the registry.
+    pub city: String,
+    #[serde(flatten)]
+    pub observation: Observation,
+}
+
+impl Record for WeatherObservation {
+    fn key(&self) -> &str {
+        &self.city
+    }
+
+    /// Midnight UTC at the start of the observed local date.
+    fn timestamp(&self) -> DateTime<Utc> {
+        self.observation.date.and_time(Default::default()).and_utc()
+    }
+}
+
+impl Record for NewsArticle {
+    fn key(&self) -> &str {
+        &self.id
+    }
+
+    fn timestamp(&self) -> DateTime<Utc> {
+        self.published_at
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock_data::cities;
+
+    #[test]
+    fn weather_days_are_updated_until_they_close() {
+        let root = std::env::temp_dir().join(format!("datapulse-{}-weather", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let store = Store::open(root).unwrap();
+        let tokyo = cities::lookup("tokyo").unwrap();
+        let record = |now: &str| record_weather(&store, tokyo, now.parse().unwrap()).unwrap();
+        let days = || {
+            store
+                .query(
+                    &WEATHER,
+                    Some("tokyo"),
+                    DateTime::UNIX_EPOCH,
+                    "2026-06-01T00:00:00Z".parse().unwrap(),
+                )
+                .unwrap()
+                .into_iter()
+                .map(|day| (day.observation.date.to_string(), day.observation.hours))
+                .collect::<Vec<_>>()
+        };
+
+        // 07:20 in Tokyo: only today so far, and no backfill before it.
+        assert_eq!(record("2026-05-03T22:20:00Z"), 1);
+        assert_eq!(days(), [("2026-05-04".to_string(), 8)]);
+        assert_eq!(record("2026-05-03T22:40:00Z"), 0);
+        assert_eq!(record("2026-05-04T05:10:00Z"), 1);
+        assert_eq!(days(), [("2026-05-04".to_string(), 15)]);
+
+        // After midnight the day is completed and the next one begins.
+        assert_eq!(record("2026-05-04T15:30:00Z"), 2);
+        assert_eq!(
+            days(),
+            [
+                ("2026-05-04".to_string(), 24),
+                ("2026-05-05".to_string(), 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn merges_price_bars_and_weighted_sentiment() {
+        let at = |minute: u32| {
+            format!("2026-03-01T10:{minute:02}:00Z")
+                .parse::<DateTime<Utc>>()
+                .unwrap()
+        };
+        let start = at(0);
+        let tick = |minute, price, volume_24h| {
+            PriceBar::from(PricePoint {
+                symbol: "BTC".to_string(),
+                timestamp: at(minute),
+                price,
+                volume_24h,
+            })
+        };
+        let bar = PriceBar::merge(
+            "BTC",
+            start,
+            &[tick(30, 101.0, 7), tick(10, 100.0, 5), tick(20, 103.0, 6)],
+        );
+        assert_eq!(
+            (
+                bar.open,
+                bar.high,
+                bar.low,
+                bar.close,
+                bar.volume_24h,
+                bar.ticks
+            ),
+            (100.0, 103.0, 100.0, 101.0, 7, 3)
+        );
+        let day = PriceBar::merge("BTC", start, &[bar.clone(), tick(40, 99.0, 8)]);
+        assert_eq!(
+            (day.open, day.low, day.close, day.ticks),
+            (100.0, 99.0, 99.0, 4)
+        );
+
+        let point = |positive, negative, mentions| SentimentPoint {
+            hashtag: "#BTC".to_string(),
+            timestamp: start,
+            positive,
+            negative,
+            neutral: 100.0 - positive - negative,
+            mentions,
+        };
+        let merged =
+            SentimentPoint::merge("#BTC", start, &[point(100.0, 0.0, 3), point(0.0, 50.0, 1)]);
+        assert_eq!(
+            (merged.positive, merged.negative, merged.mentions),
+            (75.0, 12.5, 4)
+        );
+    }
+}
@@ -0,0 +1,231 @@
+use chrono::{DateTime, SecondsFormat, Utc};
+
+use crate::mock_data::news::NewsArticle;
+
+/// Public origin used for feed and self links.
+const SITE_URL: &str = "https://datapulse.lavescar.com.tr";
+
+const FEED_TITLE: &str = "DataPulse News";
+
+const FEED_DESCRIPTION: &str = "Synthetic news headlines from the DataPulse demo";
+
+/// Render articles as an RSS 2.0 document. `category` is echoed in the
+/// channel title and self link when the feed has been filtered.
+pub fn render_rss(articles: &[NewsArticle], category: Option<&str>) -> String {
+    let self_link = self_link("rss", category);
+    let updated = last_modified(articles).unwrap_or_else(Utc::now);
+
+    let mut xml = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <rss version=\"2.0\" xmlns:atom=\"http://www.w3.org/2005/Atom\" \
+         xmlns:dc=\"http://purl.org/dc/elements/1.1/\">\n<channel>\n",
+    );
+    xml.push_str(&format!(
+        "<title>{}</title>\n<link>{SITE_URL}/news</link>\n<description>{}</description>\n\
+         <language>en</language>\n<lastBuildDate>{}</lastBuildDate>\n\
+         <atom:link href=\"{}\" rel=\"self\" type=\"application/rss+xml\"/>\n",
+        escape(&feed_title(category)),
+        escape(FEED_DESCRIPTION),
+        updated.to_rfc2822(),
+        escape(&self_link),
+    ));
+
+    for article in articles {
+        xml.push_str(&format!(
+            "<item>\n<title>{}</title>\n<link>{}</link>\n\
+             <guid isPermaLink=\"false\">{}</guid>\n<pubDate>{}</pubDate>\n\
+             <dc:creator>{}</dc:creator>\n<category>{}</category>\n\
+             <description>{}</description>\n",
+            escape(&article.title),
+            escape(&article.url),
+            escape(&article.id),
+            article.published_at.to_rfc2822(),
+            escape(&article.author),
+            escape(&article.category),
+            escape(&article.summary),
+        ));
+        if !article.image_url.is_empty() {
+            xml.push_str(&format!(
+                "<enclosure url=\"{}\" length=\"0\" type=\"{}\"/>\n",
+                escape(&article.image_url),
+                image_type(&article.image_url),
+            ));
+        }
+        xml.push_str("</item>\n");
+    }
+
+    xml.push_str("</channel>\n</rss>\n");
+    xml
+}
+
+/// Render articles as an Atom 1.0 document.
+pub fn render_atom(articles: &[NewsArticle], category: Option<&str>) -> String {
+    let self_link = self_link("atom", category);
+    let updated = last_modified(articles).unwrap_or_else(Utc::now);
+
+    let mut xml = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <feed xmlns=\"http://www.w3.org/2005/Atom\">\n",
+    );
+    xml.push_str(&format!(
+        "<title>{}</title>\n<subtitle>{}</subtitle>\n<id>{}</id>\n<updated>{}</updated>\n\
+         <link rel=\"self\" type=\"application/atom+xml\" href=\"{}\"/>\n\
+         <link rel=\"alternate\" type=\"text/html\" href=\"{SITE_URL}/news\"/>\n",
+        escape(&feed_title(category)),
+        escape(FEED_DESCRIPTION),
+        escape(&self_link),
+        updated.to_rfc3339_opts(SecondsFormat::Secs, true),
+        escape(&self_link),
+    ));
+
+    for article in articles {
+        xml.push_str(&format!(
+            "<entry>\n<title>{}</title>\n<id>{SITE_URL}/news#{}</id>\n\
+             <link rel=\"alternate\" type=\"text/html\" href=\"{}\"/>\n\
+             <published>{}</published>\n<updated>{}</updated>\n\
+             <author><name>{}</name></author>\n<category term=\"{}\"/>\n\
+             <summary type=\"text\">{}</summary>\n",
+            escape(&article.title),
+            escape(&article.id),
+            escape(&article.url),
+            article
+                .published_at
+                .to_rfc3339_opts(SecondsFormat::Secs, true),
+            article
+                .published_at
+                .to_rfc3339_opts(SecondsFormat::Secs, true),
+            escape(&article.author),
+            escape(&article.category),
+            escape(&article.summary),
+        ));
+        if !article.image_url.is_empty() {
+            xml.push_str(&format!(
+                "<link rel=\"enclosure\" type=\"{}\" href=\"{}\"/>\n",
+                image_type(&article.image_url),
+                escape(&article.image_url),
+            ));
+        }
+        xml.push_str("</entry>\n");
+    }
+
+    xml.push_str("</feed>\n");
+    xml
+}
+
+/// The newest publication time among the articles, used for `Last-Modified`.
+pub fn last_modified(articles: &[NewsArticle]) -> Option<DateTime<Utc>> {
+    articles.iter().map(|article| article.published_at).max()
+}
+
+fn feed_title(category: Option<&str>) -> String {
+    match category {
+        Some(category) => format!("{FEED_TITLE}: {category}"),
+        None => FEED_TITLE.to_string(),
+    }
+}
+
+fn self_link(format: &str, category: Option<&str>) -> String {
+    match category {
+        Some(category) => format!(
+            "{SITE_URL}/api/news/feed.{format}?category={}",
+            category.replace(' ', "%20")
+        ),
+        None => format!("{SITE_URL}/api/news/feed.{format}"),
+    }
+}
+
+/// Guess an image MIME type from the URL's extension, defaulting to JPEG.
+fn image_type(url: &str) -> &'static str {
+    let path = url.split(['?', '#']).next().unwrap_or(url).to_lowercase();
+    match path.rsplit_once('.').map(|(_, ext)| ext) {
+        Some("png") => "image/png",
+        Some("gif") => "image/gif",
+        Some("webp") => "image/webp",
+        Some("svg") => "image/svg+xml",
+        _ => "image/jpeg",
+    }
+}
+
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extract::feed::parse_feed;
+
+    fn articles() -> Vec<NewsArticle> {
+        let article = |title: &str, published_at: &str, image_url: &str| NewsArticle {
+            id: format!("news-{}", title.len()),
+            title: title.to_string(),
+            source: "Wire".to_string(),
+            published_at: published_at.parse().unwrap(),
+            category: "Science & Tech".to_string(),
+            summary: format!("{title}: summary with \"quotes\" & 'apostrophes'"),
+            url: format!("https://wire.example/{}?a=1&b=2", title.len()),
+            author: "Zoë O'Brien".to_string(),
+            word_count: 300,
+            read_time_minutes: 2,
+            image_url: image_url.to_string(),
+            keywords: Vec::new(),
+            entities: Vec::new(),
+        };
+        vec![
+            article(
+                "Rates held at 3.25% & rising",
+                "2026-10-19T07:15:00+00:00",
+                "https://wire.example/a.png",
+            ),
+            article("Probe lands", "2026-10-18T19:40:00+00:00", ""),
+        ]
+    }
+
+    fn assert_round_trips(xml: &str, expected: &[NewsArticle], title: &str) {
+        let feed = parse_feed(xml, Utc::now()).unwrap();
+        assert_eq!(feed.title, title);
+        assert_eq!(feed.articles.len(), expected.len());
+        for (parsed, original) in feed.articles.iter().zip(expected) {
+            assert_eq!(parsed.title, original.title);
+            assert_eq!(parsed.url, original.url);
+            assert_eq!(parsed.published_at, original.published_at);
+            assert_eq!(parsed.author, original.author);
+            assert_eq!(parsed.category, original.category);
+            assert_eq!(parsed.summary, original.summary);
+            assert_eq!(parsed.image_url, original.image_url);
+        }
+    }
+
+    #[test]
+    fn rss_round_trips_through_the_feed_parser() {
+        let articles = articles();
+        let xml = render_rss(&articles, Some("Science & Tech"));
+        assert!(xml.contains("<lastBuildDate>Mon, 19 Oct 2026 07:15:00 +0000</lastBuildDate>"));
+        assert!(xml.contains("feed.rss?category=Science%20&amp;%20Tech"));
+        assert!(xml.contains("type=\"image/png\""));
+        assert_round_trips(&xml, &articles, "DataPulse News: Science & Tech");
+    }
+
+    #[test]
+    fn atom_round_trips_through_the_feed_parser() {
+        let articles = articles();
+        let xml = render_atom(&articles, None);
+        assert!(xml.contains("<updated>2026-10-19T07:15:00Z</updated>"));
+        assert_round_trips(&xml, &articles, FEED_TITLE);
+        assert_eq!(
+            last_modified(&articles).unwrap().to_rfc3339(),
+            "2026-10-19T07:15:00+00:00"
+        );
+    }
+}